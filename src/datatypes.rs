@@ -3,6 +3,8 @@ use std::fmt;
 
 use anyhow::Result;
 
+use crate::format::{format_settings, FormatSettings, Label};
+use crate::tables::{custom_standard_count, custom_standard_name};
 use crate::vertical::ObstacleType;
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
@@ -11,23 +13,86 @@ pub struct Station {
     pub elevation: Option<f64>,
     pub deflection: Option<Angle>,
     pub chord: Option<f64>,
+    pub distance: Option<f64>,
+    pub offset: Option<f64>,
+    pub equation: Option<EquationTag>,
+}
+
+impl Station {
+    /// Station as it would be typed, in equated stationing with its BK or AH suffix (125+20.5 AH).
+    pub fn to_input(&self) -> String {
+        let settings = FormatSettings {
+            station_decimals: 6,
+            ..format_settings()
+        };
+        let value = self.value - self.equation.map(|x| x.shift).unwrap_or_default();
+        let station = settings.station(value);
+        let mut input = station
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string();
+        match self.equation.and_then(|x| x.side) {
+            Some(EquationSide::Back) => input += " BK",
+            Some(EquationSide::Ahead) => input += " AH",
+            None => (),
+        }
+        input
+    }
 }
 
 impl fmt::Display for Station {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let settings = format_settings();
+        let value = self.value - self.equation.map(|x| x.shift).unwrap_or_default();
         let mut cohesive_sta = format!(
-            "STA: {:.0}+{:.2}",
-            (self.value / 100.0).trunc(),
-            (self.value - (self.value / 100.0).trunc() * 100.0).abs()
+            "{} {}",
+            settings.label(Label::Station),
+            settings.station(value)
         );
+        match self.equation.and_then(|x| x.side) {
+            Some(EquationSide::Back) => cohesive_sta += " BK",
+            Some(EquationSide::Ahead) => cohesive_sta += " AH",
+            None => (),
+        }
         if let Some(elevation) = self.elevation {
-            cohesive_sta += format!(" ELEV: {:.2}", elevation).as_str();
+            cohesive_sta += format!(
+                " {} {}",
+                settings.label(Label::Elevation),
+                settings.elevation(elevation)
+            )
+            .as_str();
         }
         if let Some(deflection) = self.deflection {
-            cohesive_sta += format!(" DEFL: {:.2}", deflection).as_str();
+            cohesive_sta += format!(
+                " {} {}",
+                settings.label(Label::Deflection),
+                settings.angle(&deflection)
+            )
+            .as_str();
         }
         if let Some(chord) = self.chord {
-            cohesive_sta += format!(" CHOR: {:.2}", chord).as_str();
+            cohesive_sta += format!(
+                " {} {}",
+                settings.label(Label::Chord),
+                settings.length(chord)
+            )
+            .as_str();
+        }
+        if let Some(distance) = self.distance {
+            cohesive_sta += format!(
+                " {} {}",
+                settings.label(Label::Distance),
+                settings.length(distance)
+            )
+            .as_str();
+        }
+        if let Some(offset) = self.offset {
+            cohesive_sta += format!(
+                " {} {}",
+                settings.label(Label::Offset),
+                settings.length(offset)
+            )
+            .as_str();
         }
 
         write!(f, "{}", cohesive_sta.as_str())?;
@@ -35,7 +100,7 @@ impl fmt::Display for Station {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct CurveDetail {
     pub interval: Vec<Station>,
 }
@@ -51,7 +116,7 @@ impl fmt::Display for CurveDetail {
 
 pub type ObstacleStation = (Station, ObstacleType);
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ObstacleDetail {
     pub interval: Vec<ObstacleStation>,
 }
@@ -72,19 +137,63 @@ pub struct Angle {
 
 impl fmt::Display for Angle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "DMS: {} | DD: {:.2} | RAD: {:.2}",
-            self.to_dms(),
-            self.to_decimal_degrees(),
-            self.radians
-        )?;
+        write!(f, "{}", format_settings().angle(self))?;
         Ok(())
     }
 }
 
 impl Angle {
+    /// Signed angle, negative angles turn left. A leading sign or a trailing RT/LT gives the direction.
     pub fn from(raw_data: &str) -> Result<Self> {
+        let (angle, direction) = Self::from_directed(raw_data)?;
+
+        Ok(Angle {
+            radians: angle.radians * direction.unwrap_or_default().turn(),
+        })
+    }
+
+    /// Angle size and the direction given by a leading sign (-25.168) or a trailing RT/LT (25d10'05" RT), if any.
+    pub fn from_directed(raw_data: &str) -> Result<(Self, Option<CurveDirection>)> {
+        let trimmed = raw_data.trim();
+        let upper = trimmed.to_ascii_uppercase();
+        let (body, suffix) = match ["RT", "LT", "R", "L"]
+            .into_iter()
+            .find(|x| upper.ends_with(x))
+        {
+            Some(x) => (
+                trimmed[..trimmed.len() - x.len()].trim_end(),
+                Some(match x.starts_with('R') {
+                    true => CurveDirection::Right,
+                    false => CurveDirection::Left,
+                }),
+            ),
+            None => (trimmed, None),
+        };
+        let (body, sign) = match (body.strip_prefix('-'), body.strip_prefix('+')) {
+            (Some(x), _) => (x.trim_start(), Some(CurveDirection::Left)),
+            (_, Some(x)) => (x.trim_start(), Some(CurveDirection::Right)),
+            _ => (body, None),
+        };
+        let direction = match (sign, suffix) {
+            (Some(a), Some(b)) if a != b => return Err(Error::ConflictingDirection.into()),
+            (a, b) => a.or(b),
+        };
+
+        let decimal_degrees = Self::parse_decimal_degrees(body)?;
+        if decimal_degrees >= 360. {
+            return Err(Error::OversizedAngle.into());
+        }
+
+        Ok((
+            Angle {
+                radians: decimal_degrees * PI / 180.0,
+            },
+            direction,
+        ))
+    }
+
+    /// Decimal degrees from a DMS (12d34'56") or decimal degree string, without a size limit.
+    fn parse_decimal_degrees(raw_data: &str) -> Result<f64> {
         if !raw_data.is_empty() {
             if raw_data.chars().any(|c| matches!(c, 'd' | '\'' | '\"')) {
                 let parts = raw_data
@@ -103,23 +212,9 @@ impl Angle {
                     decimal_degrees += parts_iter.next().unwrap_or(&"0.0").parse::<f64>()? / 3600.0;
                 }
 
-                if decimal_degrees >= 180. {
-                    return Err(Error::OversizedAngle.into());
-                }
-
-                return Ok(Angle {
-                    radians: decimal_degrees * PI / 180.0,
-                });
+                return Ok(decimal_degrees);
             } else if raw_data.chars().all(|c| matches!(c, '0'..='9' | '.')) {
-                let decimal_degrees = raw_data.trim().parse::<f64>()?;
-
-                if decimal_degrees >= 180. {
-                    return Err(Error::OversizedAngle.into());
-                }
-
-                return Ok(Angle {
-                    radians: decimal_degrees * PI / 180.0,
-                });
+                return Ok(raw_data.trim().parse::<f64>()?);
             }
         }
 
@@ -127,78 +222,460 @@ impl Angle {
     }
 
     pub fn to_dms(&self) -> String {
-        let degrees = self.to_decimal_degrees().trunc();
-        let mut minutes = ((self.to_decimal_degrees() - degrees) * 60.0).trunc();
-        let mut seconds = (((self.to_decimal_degrees() - degrees) * 60.0) - minutes) * 60.0;
+        let sign = match self.radians < 0.0 {
+            true => "-",
+            false => "",
+        };
+        let decimal_degrees = self.to_decimal_degrees().abs();
+        let mut degrees = decimal_degrees.trunc();
+        let mut minutes = ((decimal_degrees - degrees) * 60.0).trunc();
+        let mut seconds = (((decimal_degrees - degrees) * 60.0) - minutes) * 60.0;
 
         if (60. - seconds).abs() < 0.000001 {
             seconds = 0.;
             minutes += 1.;
         }
+        if minutes >= 60. {
+            minutes -= 60.;
+            degrees += 1.;
+        }
 
-        format!("{:.0}d{:.0}\'{:.2}\"", degrees, minutes, seconds)
+        format!("{}{:.0}d{:.0}\'{:.2}\"", sign, degrees, minutes, seconds)
     }
 
     pub fn to_decimal_degrees(&self) -> f64 {
-        return self.radians * 180. / PI;
+        self.radians * 180. / PI
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CurveDirection {
+    #[default]
+    Right,
+    Left,
+}
+
+impl CurveDirection {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Right => Self::Left,
+            Self::Left => Self::Right,
+        }
+    }
+
+    /// 1 for curves to the right, -1 to the left.
+    pub fn turn(self) -> f64 {
+        match self {
+            Self::Right => 1.0,
+            Self::Left => -1.0,
+        }
+    }
+}
+
+impl fmt::Display for CurveDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Right => write!(f, "RT"),
+            Self::Left => write!(f, "LT"),
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum SightType {
     #[default]
     Stopping,
     Passing,
-    Decision,
+    Decision(DecisionManeuver),
 }
 
 impl SightType {
     pub fn next(self) -> Self {
         match self {
             Self::Stopping => Self::Passing,
-            Self::Passing => Self::Decision,
-            Self::Decision => Self::Stopping,
+            Self::Passing => Self::Decision(DecisionManeuver::default()),
+            Self::Decision(_) => Self::Stopping,
+        }
+    }
+
+    pub fn next_maneuver(self) -> Self {
+        match self {
+            Self::Decision(maneuver) => Self::Decision(maneuver.next()),
+            _ => self,
+        }
+    }
+}
+
+/// AASHTO avoidance maneuvers for decision sight distance.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DecisionManeuver {
+    #[default]
+    A, // stop on rural road
+    B, // stop on urban road
+    C, // speed/path/direction change on rural road
+    D, // speed/path/direction change on suburban road
+    E, // speed/path/direction change on urban road
+}
+
+impl DecisionManeuver {
+    pub fn next(self) -> Self {
+        match self {
+            Self::A => Self::B,
+            Self::B => Self::C,
+            Self::C => Self::D,
+            Self::D => Self::E,
+            Self::E => Self::A,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SpeedLookup {
+    #[default]
+    Exact,
+    Interpolate,
+    RoundUp,
+}
+
+impl SpeedLookup {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Exact => Self::Interpolate,
+            Self::Interpolate => Self::RoundUp,
+            Self::RoundUp => Self::Exact,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum DesignStandard {
     AASHTO,
     #[default]
     CALTRANS,
+    Custom(usize),
 }
 
 impl DesignStandard {
     pub fn next(self) -> Self {
+        let custom_count = custom_standard_count();
         match self {
             Self::AASHTO => Self::CALTRANS,
-            Self::CALTRANS => Self::AASHTO,
+            Self::CALTRANS if custom_count > 0 => Self::Custom(0),
+            Self::Custom(i) if i + 1 < custom_count => Self::Custom(i + 1),
+            _ => Self::AASHTO,
         }
     }
 }
 
-pub fn coerce_station_value(string: &str) -> Result<f64, Error> {
-    let mut station_vec = vec![];
-    for slice in string.split_terminator('+') {
-        station_vec.push(
-            slice
-                .trim()
-                .parse::<f64>()
-                .map_err(|x| Error::ParseNonFloat)?,
-        );
+impl fmt::Display for DesignStandard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(i) => write!(
+                f,
+                "{}",
+                custom_standard_name(*i).unwrap_or(format!("{:?}", self))
+            ),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EquationSide {
+    Back,
+    Ahead,
+}
+
+/// Difference between the true station and the displayed station, with the side when the displayed station is repeated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquationTag {
+    pub shift: f64,
+    pub side: Option<EquationSide>,
+}
+
+/// Station equation, the back station and ahead station at the same point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StationEquation {
+    pub back: f64,
+    pub ahead: f64,
+}
+
+/// Station equations in order along the alignment. Stations are kept as true stations, measured from the
+/// stationing before the first equation, and only shifted for parsing and display.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StationEquations {
+    pub equations: Vec<StationEquation>,
+}
+
+impl fmt::Display for StationEquations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        for equation in &self.equations {
+            let back = Station {
+                value: equation.back,
+                ..Default::default()
+            };
+            let ahead = Station {
+                value: equation.ahead,
+                ..Default::default()
+            };
+            writeln!(f, "{:.2} BK = {:.2} AH", back, ahead)?;
+        }
+        Ok(())
+    }
+}
+
+impl StationEquations {
+    /// True station minus displayed station after the given number of equations.
+    fn shift(&self, region: usize) -> f64 {
+        self.equations[..region]
+            .iter()
+            .map(|x| x.back - x.ahead)
+            .sum()
+    }
+
+    fn region_of_true(&self, value: f64) -> usize {
+        (0..self.equations.len())
+            .take_while(|i| value >= self.equations[*i].back + self.shift(*i))
+            .count()
+    }
+
+    /// Displayed range of a region, from the previous ahead station to the next back station.
+    fn displayed_range(&self, region: usize) -> (f64, f64) {
+        let start = match region {
+            0 => f64::NEG_INFINITY,
+            _ => self.equations[region - 1].ahead,
+        };
+        let end = match self.equations.get(region) {
+            Some(equation) => equation.back,
+            None => f64::INFINITY,
+        };
+        (start, end)
+    }
+
+    fn candidates(&self, displayed: f64) -> Vec<usize> {
+        (0..=self.equations.len())
+            .filter(|i| {
+                let (start, end) = self.displayed_range(*i);
+                displayed >= start && displayed < end
+            })
+            .collect()
+    }
+
+    /// True station of a displayed station. Repeated stations need a side, back picks the first and ahead the last.
+    pub fn to_true(&self, displayed: f64, side: Option<EquationSide>) -> Result<f64, Error> {
+        if side == Some(EquationSide::Back) {
+            if let Some(i) = self.equations.iter().position(|x| x.back == displayed) {
+                return Ok(displayed + self.shift(i));
+            }
+        }
+        let candidates = self.candidates(displayed);
+        let region = match (candidates.as_slice(), side) {
+            ([], _) => return Err(Error::StationGap),
+            ([region], _) => *region,
+            (_, None) => return Err(Error::AmbiguousStation),
+            ([first, ..], Some(EquationSide::Back)) => *first,
+            ([.., last], Some(EquationSide::Ahead)) => *last,
+        };
+
+        Ok(displayed + self.shift(region))
+    }
+
+    /// Parses a station with an optional BK or AH suffix into a true station.
+    pub fn parse(&self, string: &str) -> Result<f64, Error> {
+        let trimmed = string.trim_end();
+        let upper = trimmed.to_uppercase();
+        let (station, side) = match (upper.strip_suffix("BK"), upper.strip_suffix("AH")) {
+            (Some(_), _) => (&trimmed[..trimmed.len() - 2], Some(EquationSide::Back)),
+            (_, Some(_)) => (&trimmed[..trimmed.len() - 2], Some(EquationSide::Ahead)),
+            _ => (trimmed, None),
+        };
+
+        self.to_true(coerce_station_value(station)?, side)
     }
-    match station_vec.len() {
-        0 => return Err(Error::NoValue),
-        1 => return Err(Error::NoPlus),
-        3.. => return Err(Error::ExcessiveValues),
-        _ => (),
+
+    /// Tags a true station so it displays in equated stationing.
+    pub fn tag(&self, station: Station) -> Station {
+        if self.equations.is_empty() {
+            return Station {
+                equation: None,
+                ..station
+            };
+        }
+        let region = self.region_of_true(station.value);
+        let shift = self.shift(region);
+        let candidates = self.candidates(station.value - shift);
+        let side = match candidates.len() > 1 {
+            true if candidates.first() == Some(&region) => Some(EquationSide::Back),
+            true => Some(EquationSide::Ahead),
+            false => None,
+        };
+
+        Station {
+            equation: Some(EquationTag { shift, side }),
+            ..station
+        }
+    }
+
+    pub fn tag_detail(&self, curve_detail: &mut CurveDetail) {
+        for station in curve_detail.interval.iter_mut() {
+            *station = self.tag(*station);
+        }
+    }
+
+    /// True stations at even displayed stations from `start` (inclusive) to `end` (exclusive), restarting at each equation.
+    pub fn interval_values(&self, start: f64, end: f64, station_interval: f64) -> Vec<f64> {
+        let mut values = vec![];
+        for region in 0..=self.equations.len() {
+            let shift = self.shift(region);
+            let (region_start, region_end) = self.displayed_range(region);
+            let from = (start - shift).max(region_start);
+            let to = (end - shift).min(region_end);
+            let mut displayed = (from / station_interval).ceil() * station_interval;
+            while displayed < to && values.len() < 100 {
+                values.push(displayed + shift);
+                displayed += station_interval;
+            }
+        }
+
+        values
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub northing: f64,
+    pub easting: f64,
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let settings = format_settings();
+        write!(
+            f,
+            "{} {} {} {}",
+            settings.label(Label::Northing),
+            settings.length(self.northing),
+            settings.label(Label::Easting),
+            settings.length(self.easting)
+        )?;
+        Ok(())
+    }
+}
+
+const STATION_UNITS: [&str; 10] = [
+    "'", "ft", "foot", "feet", "m", "meter", "meters", "metre", "metres", "sta",
+];
+
+/// Reads digits with at most one decimal point from `i`, returning the number and its integer digit count.
+fn scan_number(chars: &[char], i: &mut usize) -> Option<(f64, usize)> {
+    let start = *i;
+    let mut point = None;
+    while let Some(c) = chars.get(*i) {
+        match c {
+            '0'..='9' => (),
+            '.' if point.is_none() => point = Some(*i),
+            _ => break,
+        }
+        *i += 1;
+    }
+    let slice = chars[start..*i].iter().collect::<String>();
+    let integer_digits = point.unwrap_or(*i) - start;
+    match slice.chars().any(|c| c.is_ascii_digit()) {
+        true => Some((slice.parse::<f64>().ok()?, integer_digits)),
+        false => None,
+    }
+}
+
+/// Parses stations as plain numbers (12345.67), 100 stations (123+45.67) or 1000 stations (1+234.567),
+/// with an optional alignment prefix (A 123+45), a leading minus sign, whitespace and a trailing unit (ft, m).
+/// Errors point to the offending character by its position in the string.
+pub fn coerce_station_value(string: &str) -> Result<f64, Error> {
+    let chars = string.chars().collect::<Vec<char>>();
+    let unexpected = |position: usize| Error::UnexpectedCharacter {
+        character: chars[position],
+        position,
     };
-    if station_vec[1].is_sign_negative() {
-        return Err(Error::DifferentSign);
+    let skip_whitespace = |i: &mut usize| {
+        while chars.get(*i).is_some_and(|c| c.is_whitespace()) {
+            *i += 1;
+        }
+    };
+
+    let mut i = 0;
+    skip_whitespace(&mut i);
+    // alignment prefix, letters followed by whitespace or a colon
+    let prefix_start = i;
+    while chars.get(i).is_some_and(|c| c.is_alphabetic()) {
+        i += 1;
+    }
+    if i > prefix_start {
+        match chars.get(i) {
+            Some(':') => i += 1,
+            Some(c) if c.is_whitespace() => (),
+            Some(_) => return Err(unexpected(i)),
+            None => return Err(Error::NoValue),
+        }
+        skip_whitespace(&mut i);
     }
+    if i == chars.len() {
+        return Err(Error::NoValue);
+    }
+
+    let negative = chars[i] == '-';
+    if negative {
+        i += 1;
+    }
+    let major_start = i;
+    let (major, _) = scan_number(&chars, &mut i).ok_or_else(|| match chars.get(i) {
+        Some(_) => unexpected(i),
+        None => Error::NoValue,
+    })?;
 
-    Ok(station_vec[0] * 100.0 + station_vec[1] * station_vec[0].signum())
+    let mut value = major;
+    // whitespace is allowed around the '+', as in "12 + 34"
+    let mut after_major = i;
+    skip_whitespace(&mut after_major);
+    if chars.get(after_major) == Some(&'+') {
+        i = after_major;
+        let plus = i;
+        if chars[major_start..plus].contains(&'.') {
+            let point = major_start
+                + chars[major_start..plus]
+                    .iter()
+                    .position(|c| *c == '.')
+                    .unwrap_or_default();
+            return Err(unexpected(point));
+        }
+        i += 1;
+        skip_whitespace(&mut i);
+        if chars.get(i) == Some(&'-') {
+            return Err(Error::DifferentSign { position: i });
+        }
+        let (minor, minor_digits) =
+            scan_number(&chars, &mut i).ok_or_else(|| match chars.get(i) {
+                Some(_) => unexpected(i),
+                None => Error::NoMinorValue { position: plus },
+            })?;
+        let base = match minor_digits {
+            1 | 2 => 100.0,
+            3 => 1000.0,
+            _ => return Err(Error::MinorDigits { position: plus + 1 }),
+        };
+        if chars.get(i) == Some(&'+') {
+            return Err(Error::ExcessiveValues { position: i });
+        }
+        value = major * base + minor;
+    }
+
+    skip_whitespace(&mut i);
+    let unit = chars[i..].iter().collect::<String>().to_lowercase();
+    if !unit.is_empty() && !STATION_UNITS.contains(&unit.as_str()) {
+        return Err(unexpected(i));
+    }
+    match negative {
+        true => Ok(-value),
+        false => Ok(value),
+    }
 }
 
 pub fn coerce_elevation(string: &str) -> Result<f64, Error> {
@@ -254,6 +731,217 @@ pub fn coerce_grade(string: &str) -> Result<f64, Error> {
     }
 }
 
+pub fn coerce_offsets(string: &str) -> Result<Vec<f64>, Error> {
+    string
+        .split_terminator(',')
+        .map(|x| x.trim().parse::<f64>().map_err(|x| Error::ParseOffset))
+        .collect()
+}
+
+pub fn coerce_lanes(string: &str) -> Result<usize, Error> {
+    match string.trim().parse::<usize>() {
+        Ok(0) | Err(_) => Err(Error::ParseLanes),
+        Ok(w) => Ok(w),
+    }
+}
+
+pub fn coerce_reaction_time(string: &str) -> Result<f64, Error> {
+    if string.trim().is_empty() {
+        Ok(BRAKE_REACTION_TIME)
+    } else {
+        match string.trim().parse::<f64>() {
+            Ok(x) if x >= 0.0 && x.is_finite() => Ok(x),
+            _ => Err(Error::ParseReactionTime),
+        }
+    }
+}
+
+pub fn coerce_deceleration(string: &str) -> Result<f64, Error> {
+    if string.trim().is_empty() {
+        Ok(DECELERATION_RATE)
+    } else {
+        match string.trim().parse::<f64>() {
+            Ok(x) if x > 0.0 && x.is_finite() => Ok(x),
+            Ok(x) if x.is_finite() => Err(Error::NonPositiveDeceleration),
+            _ => Err(Error::ParseDeceleration),
+        }
+    }
+}
+
+/// Two comma separated values, northing then easting.
+pub fn coerce_point(string: &str) -> Result<Point, Error> {
+    let values = string
+        .split_terminator(',')
+        .map(|x| x.trim().parse::<f64>().map_err(|x| Error::ParsePoint))
+        .collect::<Result<Vec<f64>, Error>>()?;
+    match values[..] {
+        [northing, easting] => Ok(Point { northing, easting }),
+        _ => Err(Error::ParsePoint),
+    }
+}
+
+/// Station equations separated by semicolons, back station first (125+40.22 = 125+00; 150+00 = 151+00).
+pub fn coerce_station_equations(string: &str) -> Result<StationEquations, Error> {
+    let mut equations: Vec<StationEquation> = vec![];
+    for slice in string
+        .split_terminator(';')
+        .filter(|x| !x.trim().is_empty())
+    {
+        let (back, ahead) = slice.split_once('=').ok_or(Error::ParseEquation)?;
+        let equation = StationEquation {
+            back: coerce_station_value(back.trim().trim_end_matches(['B', 'K', 'b', 'k']))?,
+            ahead: coerce_station_value(ahead.trim().trim_end_matches(['A', 'H', 'a', 'h']))?,
+        };
+        if let Some(previous) = equations.last() {
+            if equation.back <= previous.ahead {
+                return Err(Error::EquationOrder);
+            }
+        }
+        equations.push(equation);
+    }
+
+    Ok(StationEquations { equations })
+}
+
+/// Rows of a comma separated list, with their line numbers. Blank lines, `#` comments and a header row whose first cell is "sta" or "station" are skipped.
+fn list_rows(string: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    string
+        .lines()
+        .enumerate()
+        .filter(|(_, x)| !x.trim().is_empty() && !x.trim_start().starts_with('#'))
+        .enumerate()
+        .map(|(i, (line, x))| (i, line + 1, x.split(',').map(str::trim).collect::<Vec<_>>()))
+        .filter(|(i, _, cells)| {
+            !(*i == 0 && matches!(cells[0].to_lowercase().as_str(), "sta" | "station"))
+        })
+        .map(|(_, line, cells)| (line, cells))
+}
+
+fn list_error(line: usize, error: Error) -> Error {
+    Error::ListLine {
+        line,
+        error: Box::new(error),
+    }
+}
+
+/// Stations from the first column of a comma separated list, one station per line.
+pub fn coerce_station_list(
+    string: &str,
+    equations: &StationEquations,
+) -> Result<Vec<Station>, Error> {
+    list_rows(string)
+        .map(|(line, cells)| {
+            Ok(equations.tag(Station {
+                value: equations.parse(cells[0]).map_err(|x| list_error(line, x))?,
+                ..Default::default()
+            }))
+        })
+        .collect()
+}
+
+/// Obstacles from a comma separated list of station, elevation and an optional type (above or below, above when blank).
+pub fn coerce_obstacle_list(
+    string: &str,
+    equations: &StationEquations,
+) -> Result<Vec<ObstacleStation>, Error> {
+    list_rows(string)
+        .map(|(line, cells)| {
+            let value = equations.parse(cells[0]).map_err(|x| list_error(line, x))?;
+            let elevation =
+                coerce_elevation(cells.get(1).unwrap_or(&"")).map_err(|x| list_error(line, x))?;
+            let obstacle_type = match cells.get(2).map(|x| x.to_lowercase()).as_deref() {
+                None | Some("") | Some("a") | Some("above") => ObstacleType::Above,
+                Some("b") | Some("below") => ObstacleType::Below,
+                _ => return Err(list_error(line, Error::ParseObstacleType)),
+            };
+            let station = equations.tag(Station {
+                value,
+                elevation: Some(elevation),
+                ..Default::default()
+            });
+            Ok((station, obstacle_type))
+        })
+        .collect()
+}
+
+/// Points separated by semicolons (5000, 5000; 5100, 5050).
+pub fn coerce_points(string: &str) -> Result<Vec<Point>, Error> {
+    string
+        .split_terminator(';')
+        .filter(|x| !x.trim().is_empty())
+        .map(coerce_point)
+        .collect()
+}
+
+/// Station and offset separated by a comma (12+34, -5), positive offsets are right of the direction of travel.
+pub fn coerce_station_offset(
+    string: &str,
+    equations: &StationEquations,
+) -> Result<(f64, f64), Error> {
+    match string.split_once(',') {
+        Some((station, offset)) => Ok((
+            equations.parse(station)?,
+            offset
+                .trim()
+                .parse::<f64>()
+                .map_err(|x| Error::ParseOffset)?,
+        )),
+        None => Err(Error::ParseStationOffset),
+    }
+}
+
+/// Azimuth from north, either as an angle (123d45'10") or a quadrant bearing (N 56d14'50" E).
+pub fn coerce_bearing(string: &str) -> Result<Angle, Error> {
+    let trimmed = string.trim().to_uppercase();
+    let mut chars = trimmed.chars();
+    let decimal_degrees = match (chars.next(), chars.next_back()) {
+        (Some(ns @ ('N' | 'S')), Some(ew @ ('E' | 'W'))) => {
+            let angle = Angle::parse_decimal_degrees(chars.as_str().trim().to_lowercase().as_str())
+                .map_err(|x| Error::ParseBearing)?;
+            if angle > 90.0 {
+                return Err(Error::ParseBearing);
+            }
+            match (ns, ew) {
+                ('N', 'E') => angle,
+                ('S', 'E') => 180.0 - angle,
+                ('S', 'W') => 180.0 + angle,
+                _ => 360.0 - angle,
+            }
+        }
+        _ => Angle::parse_decimal_degrees(string.trim()).map_err(|x| Error::ParseBearing)?,
+    };
+    if decimal_degrees >= 360.0 {
+        return Err(Error::ParseBearing);
+    }
+
+    Ok(Angle {
+        radians: decimal_degrees * PI / 180.0,
+    })
+}
+
+/// AASHTO brake reaction time (s).
+pub const BRAKE_REACTION_TIME: f64 = 2.5;
+/// AASHTO deceleration rate (ft/s^2).
+pub const DECELERATION_RATE: f64 = 11.2;
+
+/// Stopping sight distance, AASHTO eq. 3-3. Grade is negative for downgrades.
+pub fn calc_stopping_sight(
+    design_speed: f64,
+    reaction_time: f64,
+    deceleration: f64,
+    grade: f64,
+) -> Result<f64, Error> {
+    if deceleration <= 0.0 {
+        return Err(Error::NonPositiveDeceleration);
+    }
+    // braking can't stop the vehicle on a downgrade steeper than a/g
+    let braking = deceleration / 32.2 + grade;
+    if braking <= 0.0 {
+        return Err(Error::NoBraking);
+    }
+    Ok(1.47 * design_speed * reaction_time + design_speed.powi(2) / (30.0 * braking))
+}
+
 pub fn calc_adjustment(bools: bool) -> f64 {
     let mut adjustment = 1.;
     if bools {
@@ -268,21 +956,24 @@ pub enum Error {
     /// Station is misconfigured.
     #[error("Station is misconfigured.")]
     ParseStation,
-    /// Station is misconfigured with unexpected symbol.
-    #[error("Station is misconfigured with unexpected symbol.")]
-    ParseNonFloat,
+    /// Station has an unexpected character.
+    #[error("Station has an unexpected '{character}' at character {}.", .position + 1)]
+    UnexpectedCharacter { character: char, position: usize },
     /// Station is required.
     #[error("Station is required.")]
     NoValue,
-    /// Station requires a "+" sign.
-    #[error("Station requires a \"+\" sign.")]
-    NoPlus,
+    /// Station needs a value after the "+" sign.
+    #[error("Station needs a value after the \"+\" at character {}.", .position + 1)]
+    NoMinorValue { position: usize },
+    /// Station needs 2 (12+34) or 3 (1+234) digits after the "+" sign.
+    #[error("Station needs 2 or 3 digits after the \"+\", at character {}.", .position + 1)]
+    MinorDigits { position: usize },
     /// Station can only have one "+" sign.
-    #[error("Station can only have one \"+\" sign.")]
-    ExcessiveValues,
-    /// Only one negative sign is required.
-    #[error("Only one negative sign is required.")]
-    DifferentSign,
+    #[error("Station can only have one \"+\" sign, at character {}.", .position + 1)]
+    ExcessiveValues { position: usize },
+    /// Only the leading negative sign is allowed.
+    #[error("Station can only be negative with a leading \"-\", at character {}.", .position + 1)]
+    DifferentSign { position: usize },
     /// Elevation is misconfigured.
     #[error("Elevation is misconfigured with unexpected symbol.")]
     ParseElevation,
@@ -304,19 +995,233 @@ pub enum Error {
     /// Grade is misconfigured with unexpected symbol.
     #[error("Grade is misconfigured with unexpected symbol.")]
     ParseGrade,
+    /// Offsets are misconfigured with unexpected symbol.
+    #[error("Offsets are misconfigured with unexpected symbol.")]
+    ParseOffset,
+    /// Lanes are misconfigured, a whole number of lanes is required.
+    #[error("Lanes are misconfigured, a whole number of lanes is required.")]
+    ParseLanes,
+    /// Reaction time is misconfigured, negative or not finite.
+    #[error("Reaction time must be a number of seconds, zero or more.")]
+    ParseReactionTime,
+    /// Deceleration is misconfigured with unexpected symbol.
+    #[error("Deceleration is misconfigured with unexpected symbol.")]
+    ParseDeceleration,
+    /// Deceleration must be positive.
+    #[error("Deceleration must be greater than zero.")]
+    NonPositiveDeceleration,
+    /// Downgrade is too steep for the deceleration to stop on.
+    #[error("Downgrade is too steep to stop on at this deceleration.")]
+    NoBraking,
     /// Angle is misconfigured with unexpected symbol.
     #[error("Angle is misconfigured with unexpected symbol.")]
     ParseAngle,
-    /// Angle is too large.
-    #[error("Angle is too large.")]
+    /// Angle is a full circle or larger.
+    #[error("Angle must be less than 360 degrees.")]
     OversizedAngle,
+    /// Angle sign and RT/LT suffix disagree.
+    #[error("Angle sign and RT/LT direction disagree.")]
+    ConflictingDirection,
+    /// Obstacle type isn't above or below.
+    #[error("Obstacle type must be above or below.")]
+    ParseObstacleType,
+    /// A line of an imported list doesn't parse.
+    #[error("Line {line}: {error}")]
+    ListLine { line: usize, error: Box<Error> },
+    /// Point is misconfigured, two comma separated values are required.
+    #[error("Point is misconfigured, two comma separated values are required.")]
+    ParsePoint,
+    /// Bearing is misconfigured.
+    #[error("Bearing is misconfigured, use an azimuth or a quadrant bearing.")]
+    ParseBearing,
+    /// Station and offset are misconfigured.
+    #[error(
+        "Station and offset are misconfigured, use a station and an offset separated by a comma."
+    )]
+    ParseStationOffset,
+    /// Station equation is misconfigured.
+    #[error("Station equation is misconfigured, use back = ahead (125+40.22 = 125+00).")]
+    ParseEquation,
+    /// Station equations are out of order.
+    #[error(
+        "Station equations must be in order, each back station after the previous ahead station."
+    )]
+    EquationOrder,
+    /// Station is skipped by a station equation.
+    #[error("Station is skipped by a station equation.")]
+    StationGap,
+    /// Station is repeated by a station equation.
+    #[error("Station is repeated by a station equation, add BK or AH.")]
+    AmbiguousStation,
+}
+
+impl Error {
+    /// Position of the offending character, for station errors that have one.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            Self::UnexpectedCharacter { position, .. }
+            | Self::NoMinorValue { position }
+            | Self::MinorDigits { position }
+            | Self::ExcessiveValues { position }
+            | Self::DifferentSign { position } => Some(*position),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod data_tests {
-    use crate::datatypes::Angle;
+    use crate::datatypes::{
+        calc_stopping_sight, coerce_bearing, coerce_deceleration, coerce_obstacle_list,
+        coerce_point, coerce_reaction_time, coerce_station_equations, coerce_station_list,
+        coerce_station_value, Angle, CurveDirection, Station, BRAKE_REACTION_TIME,
+        DECELERATION_RATE,
+    };
+    use crate::vertical::ObstacleType;
     use anyhow::Result;
 
+    #[test]
+    fn stopping_sight() {
+        let level = calc_stopping_sight(60., BRAKE_REACTION_TIME, DECELERATION_RATE, 0.).unwrap();
+        assert!((level - 570.).abs() < 10.);
+        let downgrade =
+            calc_stopping_sight(60., BRAKE_REACTION_TIME, DECELERATION_RATE, -0.06).unwrap();
+        assert!(downgrade > level);
+        assert!(calc_stopping_sight(60., BRAKE_REACTION_TIME, 0., 0.).is_err());
+        assert!(calc_stopping_sight(60., BRAKE_REACTION_TIME, DECELERATION_RATE, -0.4).is_err());
+
+        assert_eq!(coerce_reaction_time("").unwrap(), BRAKE_REACTION_TIME);
+        assert_eq!(coerce_reaction_time("0").unwrap(), 0.);
+        assert!(coerce_reaction_time("-1").is_err());
+        assert!(coerce_reaction_time("inf").is_err());
+        assert!(coerce_reaction_time("NaN").is_err());
+        assert_eq!(coerce_deceleration("").unwrap(), DECELERATION_RATE);
+        assert!(coerce_deceleration("0").is_err());
+        assert!(coerce_deceleration("-11.2").is_err());
+    }
+
+    #[test]
+    fn station_notations() {
+        let stations = [
+            ("123+45.67", 12345.67),
+            ("12345.67", 12345.67),
+            ("1+234.567", 1234.567),
+            ("A 123+45", 12345.),
+            ("ML: 12+00", 1200.),
+            ("  -1+50 ", -150.),
+            ("-0+50", -50.),
+            ("12+5", 1205.),
+            ("123+45.67 ft", 12345.67),
+            ("1+234.5m", 1234.5),
+            ("00+25", 25.),
+            ("12 + 34", 1234.),
+            ("12 +34.5", 1234.5),
+            ("A 1 + 234", 1234.),
+        ];
+        for (station, value) in stations {
+            assert!(
+                (coerce_station_value(station).unwrap() - value).abs() < 1e-9,
+                "{}",
+                station
+            );
+        }
+
+        let errors = [
+            ("12+3x4", Some(4)),
+            ("12+-34", Some(3)),
+            ("12+34+56", Some(5)),
+            ("12+3456", Some(3)),
+            ("12.5+30", Some(2)),
+            ("12+", Some(2)),
+            ("12+34 km", Some(6)),
+            ("A12+34", Some(1)),
+            ("", None),
+            ("  A  ", None),
+        ];
+        for (station, position) in errors {
+            let error = coerce_station_value(station).unwrap_err();
+            assert_eq!(error.position(), position, "{}", station);
+        }
+        assert!(format!("{}", coerce_station_value("12+3x4").unwrap_err())
+            .contains("'x' at character 5"));
+    }
+
+    #[test]
+    fn imported_lists() {
+        let equations = coerce_station_equations("125+40.22 BK = 125+00 AH").unwrap();
+        let obstacles = coerce_obstacle_list(
+            "Station, Elevation, Type\n# bridge and pipe\n12+00, 105.5\n\n125+20 AH, 98, below\n",
+            &equations,
+        )
+        .unwrap();
+        assert_eq!(obstacles.len(), 2);
+        assert_eq!(obstacles[0].0.value, 1200.);
+        assert!(matches!(obstacles[1].1, ObstacleType::Below));
+        assert_eq!(obstacles[1].0.to_input(), "125+20 AH");
+
+        let error = coerce_obstacle_list("12+00, 100\n12+50, 100, sideways", &equations)
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("Line 2:"));
+        assert!(coerce_obstacle_list("12+00", &equations).is_err());
+
+        // a first row that is a station is data, not a header
+        let obstacles = coerce_obstacle_list("STA 12+00, 100\n12+50, 101", &equations).unwrap();
+        assert_eq!(obstacles.len(), 2);
+        assert_eq!(obstacles[0].0.value, 1200.);
+        let error = coerce_obstacle_list("Stn, Elev\n12+00, 100", &equations).unwrap_err();
+        assert!(error.to_string().starts_with("Line 1:"));
+
+        let pins = coerce_station_list("12+10.5\n12+60, note", &equations).unwrap();
+        assert_eq!(pins[0].to_input(), "12+10.5");
+        assert_eq!(pins[1].value, 1260.);
+    }
+
+    #[test]
+    fn station_equations() {
+        let equations =
+            coerce_station_equations("125+40.22 BK = 125+00 AH; 150+00 = 151+00").unwrap();
+        assert_eq!(equations.parse("120+00").unwrap(), 12000.);
+        assert!(equations.parse("125+20").is_err());
+        assert_eq!(equations.parse("125+20 BK").unwrap(), 12520.);
+        assert!((equations.parse("125+20 ah").unwrap() - 12560.22).abs() < 1e-9);
+        assert!(equations.parse("150+50").is_err());
+        assert!((equations.parse("152+00").unwrap() - 15140.22).abs() < 1e-9);
+
+        let station = equations.tag(Station {
+            value: 12560.22,
+            ..Default::default()
+        });
+        assert_eq!(format!("{}", station), "STA: 125+20.00 AH");
+
+        let values = equations.interval_values(12500., 12600., 25.);
+        let expected = [12500., 12525., 12540.22, 12565.22, 12590.22];
+        assert_eq!(values.len(), expected.len());
+        assert!(values
+            .iter()
+            .zip(expected)
+            .all(|(a, b)| (a - b).abs() < 1e-9));
+
+        assert!(coerce_station_equations("125+00 = 124+00; 123+50 = 130+00").is_err());
+    }
+
+    #[test]
+    fn bearings_and_points() {
+        let azimuth = coerce_bearing("123d30'").unwrap();
+        assert!((azimuth.to_decimal_degrees() - 123.5).abs() < 1e-9);
+        let quadrant = coerce_bearing("S 56d30' E").unwrap();
+        assert!((quadrant.to_decimal_degrees() - 123.5).abs() < 1e-9);
+        let quadrant = coerce_bearing("n10w").unwrap();
+        assert!((quadrant.to_decimal_degrees() - 350.).abs() < 1e-9);
+        assert!(coerce_bearing("N 95 E").is_err());
+        assert!(coerce_bearing("360").is_err());
+
+        let point = coerce_point("5000, 2500.5").unwrap();
+        assert_eq!(point.northing, 5000.);
+        assert_eq!(point.easting, 2500.5);
+        assert!(coerce_point("5000").is_err());
+    }
+
     #[test]
     fn from_angle() {
         let angles = vec![
@@ -336,6 +1241,30 @@ mod data_tests {
         }
     }
 
+    #[test]
+    fn signed_angles() {
+        let angles = [
+            ("25d10'05\" RT", 25.168),
+            ("25d10'05\"lt", -25.168),
+            ("-25.168", -25.168),
+            ("+25.168 R", 25.168),
+            ("200", 200.),
+            ("359d59'", 359.983),
+        ];
+        for (angle, degrees) in angles {
+            let angle = Angle::from(angle).unwrap();
+            assert!((angle.to_decimal_degrees() - degrees).abs() < 1e-3);
+        }
+        assert_eq!(Angle::from("-10d30'").unwrap().to_dms(), "-10d30'0.00\"");
+        assert_eq!(
+            Angle::from_directed("12").unwrap().1,
+            None::<CurveDirection>
+        );
+        assert!(Angle::from("360").is_err());
+        assert!(Angle::from("-25 RT").is_err());
+        assert!(Angle::from("25 XT").is_err());
+    }
+
     #[test]
     fn dd_dms_dd_eq() -> Result<()> {
         let angles = vec![
@@ -7,20 +7,21 @@ use std::io::prelude::*;
 
 use crate::datatypes::*;
 use crate::export::liberation_sans;
+use crate::format::fmt_length;
 use crate::horizontal::HorizontalData;
 
 impl HorizontalData {
-    pub fn export_txt(&self) -> Result<()> {
-        let mut file = File::create(self.input_directory.clone() + ".txt")?;
+    pub fn export_txt(&self, path: &str) -> Result<()> {
+        let mut file = File::create(path.to_string() + ".txt")?;
 
         write!(file, "{}", self.to_txt()?)?;
         Ok(())
     }
 
-    pub fn export_pdf(&self) -> Result<()> {
+    pub fn export_pdf(&self, path: &str) -> Result<()> {
         let doc = self.to_pdf()?;
 
-        doc.render_to_file(self.input_directory.clone() + ".pdf")?;
+        doc.render_to_file(path.to_string() + ".pdf")?;
         Ok(())
     }
 
@@ -33,34 +34,43 @@ impl HorizontalData {
         )
         .as_str();
         buf += format!("\nMajor Stations\n--\n{}", curve.stations).as_str();
+        if !curve.equations.equations.is_empty() {
+            buf += format!("Station Equations\n--\n{}", curve.equations).as_str();
+        }
+        if let Ok(points) = curve.major_points() {
+            buf += format!("{}", points).as_str();
+        }
 
         if !self.input_design_speed.is_empty() && !self.input_m.is_empty() {
             match curve.is_compliant(
                 self.input_design_standard,
                 self.input_sight_type,
+                self.input_speed_lookup,
                 calc_adjustment(self.sustained_downgrade),
             ) {
                 Err(e) => {}
                 Ok(j) => {
                     if j.0 {
                         buf += format!(
-                            "\nSight Distance Validation ({:?} - {:?})\n--\n[COMPLIANT] {:.2} > {:.2}",
+                            "\nSight Distance Validation ({} - {:?})\n--\n[COMPLIANT] {} > {}",
                             self.input_design_standard,
                             self.input_sight_type,
-                            curve.dimensions.sight_distance,
-                            j.1
+                            fmt_length(curve.dimensions.sight_distance),
+                            fmt_length(j.1)
                         )
                         .as_str();
                     } else {
                         buf += format!(
-                            "\nSight Distance Validation ({:?} - {:?})\n--\n[NONCOMPLIANT] {:.2} < {:.2}",
+                            "\nSight Distance Validation ({} - {:?})\n--\n[NONCOMPLIANT] {} < {}",
                             self.input_design_standard,
                             self.input_sight_type,
-                            curve.dimensions.sight_distance,
-                            j.1
+                            fmt_length(curve.dimensions.sight_distance),
+                            fmt_length(j.1)
                         )
                         .as_str();
                     }
+                    buf += format!("\n{}", j.2).as_str();
+                    buf += format!("\n{}", curve.hso_detail(j.1)).as_str();
                 }
             }
         }
@@ -69,11 +79,60 @@ impl HorizontalData {
             buf += "\nThis horizontal curve experiences a sustained downgrade.";
         }
 
+        if !self.input_traveled_way.is_empty() {
+            if let Ok(w) = self.to_widening(&curve) {
+                buf += format!(
+                    "\n\nTraveled Way Widening ({:?})\n--\n{}",
+                    self.input_design_vehicle, w
+                )
+                .as_str();
+            }
+        }
+
+        if let Ok(offsets) = coerce_offsets(&self.input_offsets) {
+            let interval = coerce_station_value(&self.input_station_interval);
+            for offset in offsets {
+                match curve.offset_curve(offset) {
+                    Err(e) => {
+                        buf +=
+                            format!("\n\nOffset Curve\n--\n{}: {}", fmt_length(offset), e).as_str()
+                    }
+                    Ok(offset_curve) => {
+                        buf += format!("\n\nOffset Curve\n--\n{}", offset_curve).as_str();
+                        if let Ok(t) = interval {
+                            buf += format!(
+                                "Radial to centerline stations\n{}",
+                                curve.radial_interval_stations(&offset_curve, t)
+                            )
+                            .as_str();
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Ok(points) = coerce_points(&self.input_locate_points) {
+            if !points.is_empty() {
+                buf += "\n\nPoint Locations\n--\n";
+            }
+            for point in points {
+                if let Ok(location) = curve.locate_point(point) {
+                    buf += format!("> {}\n", location).as_str();
+                }
+            }
+        }
+
         if !self.input_station_interval.is_empty()
             && coerce_station_value(&self.input_station_interval).is_ok()
         {
             let t = coerce_station_value(&self.input_station_interval).unwrap_or_default();
-            buf += format!("\n\nInterval Stations\n--\n{}", curve.interval_stations(t)).as_str();
+            if let Ok(detail) = self.to_stakeout(&curve, t) {
+                buf += format!(
+                    "\n\nInterval Stations ({})\n--\n{}",
+                    self.input_stakeout_method, detail
+                )
+                .as_str();
+            }
         }
 
         buf += format!("\nDate of Production: {}", Local::now()).as_str();
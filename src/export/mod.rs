@@ -2,10 +2,12 @@ use anyhow::Result;
 use genpdf::fonts::{FontData, FontFamily};
 use native_dialog::FileDialog;
 
+use crate::datatypes::Station;
+
 pub mod horizontal;
 pub mod vertical;
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum ExportSuccess {
     Failure,
     #[default]
@@ -13,8 +15,13 @@ pub enum ExportSuccess {
     Success,
 }
 
-pub fn save_to() -> String {
-    match FileDialog::new().show_save_single_file() {
+/// Save dialog opened in the given folder with a suggested file name.
+pub fn save_to(location: &str, filename: &str) -> String {
+    match FileDialog::new()
+        .set_location(location)
+        .set_filename(filename)
+        .show_save_single_file()
+    {
         Err(e) => "".to_string(),
         Ok(w) => w
             .unwrap_or_default()
@@ -24,6 +31,40 @@ pub fn save_to() -> String {
     }
 }
 
+pub fn open_from() -> String {
+    match FileDialog::new().show_open_single_file() {
+        Err(e) => "".to_string(),
+        Ok(w) => w
+            .unwrap_or_default()
+            .to_str()
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+pub fn folder_from() -> String {
+    match FileDialog::new().show_open_single_dir() {
+        Err(e) => "".to_string(),
+        Ok(w) => w
+            .unwrap_or_default()
+            .to_str()
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/// Export file name from the project name and the curve's key station, e.g. Curve_1_PVI_12+34.56.
+pub fn export_file_name(name: &str, label: &str, station: &Station) -> String {
+    let station = station.to_input().replace(' ', "_");
+    format!("{}_{}_{}", name.trim(), label, station)
+        .chars()
+        .map(|c| match c.is_alphanumeric() || "+-._".contains(c) {
+            true => c,
+            false => '_',
+        })
+        .collect()
+}
+
 pub fn liberation_sans() -> Result<FontFamily<FontData>> {
     Ok(FontFamily {
         regular: FontData::new(
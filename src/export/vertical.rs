@@ -7,20 +7,22 @@ use std::io::prelude::*;
 
 use crate::datatypes::*;
 use crate::export::liberation_sans;
+use crate::format::{fmt_elevation, fmt_k, fmt_length};
+use crate::tables::get_min_sight;
 use crate::vertical::{calculate::ObstacleReturn, VerticalData};
 
 impl VerticalData {
-    pub fn export_txt(&self) -> Result<()> {
-        let mut file = File::create(self.input_directory.clone() + ".txt")?;
+    pub fn export_txt(&self, path: &str) -> Result<()> {
+        let mut file = File::create(path.to_string() + ".txt")?;
 
         write!(file, "{}", self.to_txt()?)?;
         Ok(())
     }
 
-    pub fn export_pdf(&self) -> Result<()> {
+    pub fn export_pdf(&self, path: &str) -> Result<()> {
         let doc = self.to_pdf()?;
 
-        doc.render_to_file(self.input_directory.clone() + ".pdf")?;
+        doc.render_to_file(path.to_string() + ".pdf")?;
         Ok(())
     }
 
@@ -29,34 +31,117 @@ impl VerticalData {
         let curve = self.to_vertical_curve()?;
         buf += format!("Vertical Curve\n\nCurve Details\n--\n{}", curve.dimensions).as_str();
         buf += format!("\nMajor Stations\n--\n{}", curve.stations).as_str();
+        if !curve.equations.equations.is_empty() {
+            buf += format!("Station Equations\n--\n{}", curve.equations).as_str();
+        }
 
         if !self.input_design_speed.is_empty() {
             match curve.is_compliant(
                 self.input_design_standard,
                 self.input_sight_type,
+                self.input_speed_lookup,
                 calc_adjustment(self.sustained_downgrade),
             ) {
                 Err(e) => {}
                 Ok(j) => {
                     if j.0 {
                         buf += format!(
-                            "\nSight Distance Validation ({:?} - {:?})\n--\n[COMPLIANT] {:.2} > {:.2}",
+                            "\nSight Distance Validation ({} - {:?})\n--\n[COMPLIANT] {} > {}",
                             self.input_design_standard,
                             self.input_sight_type,
-                            curve.dimensions.curve_length,
-                            j.1
+                            fmt_length(curve.dimensions.curve_length),
+                            fmt_length(j.1)
                         )
                         .as_str();
                     } else {
                         buf += format!(
-                            "\nSight Distance Validation ({:?} - {:?})\n--\n[NONCOMPLIANT] {:.2} < {:.2}",
+                            "\nSight Distance Validation ({} - {:?})\n--\n[NONCOMPLIANT] {} < {}",
                             self.input_design_standard,
                             self.input_sight_type,
-                            curve.dimensions.curve_length,
-                            j.1
+                            fmt_length(curve.dimensions.curve_length),
+                            fmt_length(j.1)
                         )
                         .as_str();
                     }
+                    buf += format!("\n{}", j.2).as_str();
+                }
+            }
+        }
+
+        if !self.input_design_speed.is_empty() {
+            if let Ok(j) = curve.is_k_compliant(self.input_design_standard, self.input_speed_lookup)
+            {
+                if j.0 {
+                    buf += format!(
+                        "\n\nK Value Validation ({})\n--\n[COMPLIANT] {} > {}",
+                        self.input_design_standard,
+                        fmt_k(curve.dimensions.k_value),
+                        fmt_length(j.1)
+                    )
+                    .as_str();
+                } else {
+                    buf += format!(
+                        "\n\nK Value Validation ({})\n--\n[NONCOMPLIANT] {} < {}",
+                        self.input_design_standard,
+                        fmt_k(curve.dimensions.k_value),
+                        fmt_length(j.1)
+                    )
+                    .as_str();
+                }
+                buf += format!("\n{}", j.2).as_str();
+            }
+        }
+
+        if let (false, Ok(t), Ok(a)) = (
+            self.input_design_speed.is_empty(),
+            coerce_reaction_time(&self.input_reaction_time),
+            coerce_deceleration(&self.input_deceleration),
+        ) {
+            match curve.grade_adjusted_sight(t, a) {
+                Err(e) => {
+                    buf += format!(
+                        "\n\nGrade-Adjusted Stopping Sight ({})\n--\n{}",
+                        self.input_design_standard, e
+                    )
+                    .as_str()
+                }
+                Ok((ahead, back)) => {
+                    buf += format!(
+                "\n\nGrade-Adjusted Stopping Sight ({})\n--\nAhead ({:.2}%): {}\nBack ({:.2}%): {}",
+                self.input_design_standard,
+                curve.dimensions.outgoing_grade * 100.0,
+                fmt_length(ahead),
+                -curve.dimensions.incoming_grade * 100.0,
+                fmt_length(back)
+            )
+                    .as_str();
+                    if let Some(w) = get_min_sight(
+                        curve.dimensions.design_speed,
+                        self.input_design_standard,
+                        SightType::Stopping,
+                        self.input_speed_lookup,
+                    ) {
+                        buf += format!("\n{}", w).as_str();
+                    }
+                    if let Ok(j) =
+                        curve.is_grade_adjusted_compliant(self.input_design_standard, t, a)
+                    {
+                        if j.0 {
+                            buf += format!(
+                                "\n[COMPLIANT] {} > {}",
+                                fmt_length(curve.dimensions.curve_length),
+                                fmt_length(j.1)
+                            )
+                            .as_str();
+                        } else {
+                            buf += format!(
+                                "\n[NONCOMPLIANT] {} < {}",
+                                fmt_length(curve.dimensions.curve_length),
+                                fmt_length(j.1)
+                            )
+                            .as_str();
+                        }
+                    }
                 }
             }
         }
@@ -74,14 +159,20 @@ impl VerticalData {
                     Ok(w) => {
                         if w.0 {
                             buf += format!(
-                                "\n[COMPLIANT] Obstacle: {} {:?}\nActual: {}\nDelta: {:2}",
-                                w.1 .0, w.1 .1, w.2, w.3
+                                "\n[COMPLIANT] Obstacle: {} {:?}\nActual: {}\nDelta: {}",
+                                w.1 .0,
+                                w.1 .1,
+                                w.2,
+                                fmt_elevation(w.3)
                             )
                             .as_str();
                         } else {
                             buf += format!(
-                                "\n[NONCOMPLIANT] Obstacle: {} {:?}\nActual: {}\nDelta: {:2}",
-                                w.1 .0, w.1 .1, w.2, w.3
+                                "\n[NONCOMPLIANT] Obstacle: {} {:?}\nActual: {}\nDelta: {}",
+                                w.1 .0,
+                                w.1 .1,
+                                w.2,
+                                fmt_elevation(w.3)
                             )
                             .as_str();
                         }
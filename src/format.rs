@@ -0,0 +1,306 @@
+use lazy_static::lazy_static;
+use std::fmt;
+use std::sync::RwLock;
+
+use crate::datatypes::Angle;
+
+lazy_static! {
+    static ref FORMAT_SETTINGS: RwLock<FormatSettings> = RwLock::new(FormatSettings::default());
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StationBase {
+    #[default]
+    Hundred, // 12+34.56
+    Thousand, // 1+234.56
+}
+
+impl StationBase {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Hundred => Self::Thousand,
+            Self::Thousand => Self::Hundred,
+        }
+    }
+
+    pub fn value(self) -> f64 {
+        match self {
+            Self::Hundred => 100.0,
+            Self::Thousand => 1000.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AngleFormat {
+    #[default]
+    All,
+    DMS,
+    DD,
+    RAD,
+}
+
+impl AngleFormat {
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::DMS,
+            Self::DMS => Self::DD,
+            Self::DD => Self::RAD,
+            Self::RAD => Self::All,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LabelStyle {
+    #[default]
+    Short, // STA: ELEV:
+    Long, // Station: Elevation:
+}
+
+impl LabelStyle {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Short => Self::Long,
+            Self::Long => Self::Short,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Units {
+    #[default]
+    Feet,
+    Metres,
+}
+
+impl Units {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Feet => Self::Metres,
+            Self::Metres => Self::Feet,
+        }
+    }
+
+    /// Usual station base, 10+00 in feet and 1+000 in metres.
+    pub fn station_base(self) -> StationBase {
+        match self {
+            Self::Feet => StationBase::Hundred,
+            Self::Metres => StationBase::Thousand,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Label {
+    Station,
+    Elevation,
+    Deflection,
+    Chord,
+    Distance,
+    Offset,
+    Northing,
+    Easting,
+}
+
+/// Number and label formatting shared by the GUI and the exports.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FormatSettings {
+    pub station_decimals: usize,
+    pub elevation_decimals: usize,
+    pub length_decimals: usize,
+    pub station_base: StationBase,
+    pub angle_format: AngleFormat,
+    pub label_style: LabelStyle,
+    pub units: Units,
+}
+
+impl Default for FormatSettings {
+    fn default() -> Self {
+        FormatSettings {
+            station_decimals: 2,
+            elevation_decimals: 2,
+            length_decimals: 2,
+            station_base: StationBase::default(),
+            angle_format: AngleFormat::default(),
+            label_style: LabelStyle::default(),
+            units: Units::default(),
+        }
+    }
+}
+
+impl fmt::Display for FormatSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Station {} ({:?}) | Elevation {} | Length {} | Angle {:?} | Labels {:?} | Units {:?}",
+            self.station_decimals,
+            self.station_base,
+            self.elevation_decimals,
+            self.length_decimals,
+            self.angle_format,
+            self.label_style,
+            self.units
+        )
+    }
+}
+
+/// Cycles 0 to 4 decimals.
+pub fn next_decimals(decimals: usize) -> usize {
+    (decimals + 1) % 5
+}
+
+impl FormatSettings {
+    pub fn station(&self, value: f64) -> String {
+        let base = self.station_base.value();
+        let mut major = (value / base).trunc();
+        let mut minor = (value - major * base).abs();
+        // rounding can carry the minor part up to the base
+        if format!("{:.*}", self.station_decimals, minor).parse::<f64>() == Ok(base) {
+            major += value.signum();
+            minor = 0.0;
+        }
+        let width = match self.station_decimals {
+            0 => self.station_base.value().log10() as usize,
+            d => self.station_base.value().log10() as usize + 1 + d,
+        };
+        let sign = match value < 0.0 {
+            true => "-",
+            false => "",
+        };
+
+        format!(
+            "{}{:.0}+{:0width$.decimals$}",
+            sign,
+            major.abs(),
+            minor,
+            width = width,
+            decimals = self.station_decimals
+        )
+    }
+
+    pub fn elevation(&self, value: f64) -> String {
+        format!("{:.*}", self.elevation_decimals, value)
+    }
+
+    pub fn length(&self, value: f64) -> String {
+        format!("{:.*}", self.length_decimals, value)
+    }
+
+    pub fn angle(&self, angle: &Angle) -> String {
+        match self.angle_format {
+            AngleFormat::All => format!(
+                "DMS: {} | DD: {:.2} | RAD: {:.2}",
+                angle.to_dms(),
+                angle.to_decimal_degrees(),
+                angle.radians
+            ),
+            AngleFormat::DMS => angle.to_dms(),
+            AngleFormat::DD => format!("{:.4}°", angle.to_decimal_degrees()),
+            AngleFormat::RAD => format!("{:.6} rad", angle.radians),
+        }
+    }
+
+    /// Label before a value, lengths are marked when they are in metres.
+    pub fn label(&self, label: Label) -> String {
+        let name = match (self.label_style, label) {
+            (LabelStyle::Short, Label::Station) => "STA:",
+            (LabelStyle::Short, Label::Elevation) => "ELEV:",
+            (LabelStyle::Short, Label::Deflection) => "DEFL:",
+            (LabelStyle::Short, Label::Chord) => "CHOR:",
+            (LabelStyle::Short, Label::Distance) => "DIST:",
+            (LabelStyle::Short, Label::Offset) => "OFFS:",
+            (LabelStyle::Short, Label::Northing) => "N:",
+            (LabelStyle::Short, Label::Easting) => "E:",
+            (LabelStyle::Long, Label::Station) => "Station:",
+            (LabelStyle::Long, Label::Elevation) => "Elevation:",
+            (LabelStyle::Long, Label::Deflection) => "Deflection:",
+            (LabelStyle::Long, Label::Chord) => "Chord:",
+            (LabelStyle::Long, Label::Distance) => "Distance:",
+            (LabelStyle::Long, Label::Offset) => "Offset:",
+            (LabelStyle::Long, Label::Northing) => "Northing:",
+            (LabelStyle::Long, Label::Easting) => "Easting:",
+        };
+        match (self.units, label) {
+            (Units::Feet, _) | (_, Label::Station | Label::Deflection) => name.to_string(),
+            (Units::Metres, _) => format!("{} (m):", name.trim_end_matches(':')),
+        }
+    }
+}
+
+pub fn format_settings() -> FormatSettings {
+    FORMAT_SETTINGS.read().map(|x| *x).unwrap_or_default()
+}
+
+pub fn set_format_settings(settings: FormatSettings) {
+    if let Ok(mut w) = FORMAT_SETTINGS.write() {
+        *w = settings;
+    }
+}
+
+/// Formats a length with the current settings.
+pub fn fmt_length(value: f64) -> String {
+    format_settings().length(value)
+}
+
+/// Formats a K value, which doesn't apply when the grades are equal.
+pub fn fmt_k(value: Option<f64>) -> String {
+    value.map_or("N/A".to_string(), fmt_length)
+}
+
+/// Formats an elevation with the current settings.
+pub fn fmt_elevation(value: f64) -> String {
+    format_settings().elevation(value)
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::{AngleFormat, FormatSettings, Label, LabelStyle, StationBase, Units};
+    use crate::datatypes::Angle;
+
+    #[test]
+    fn stations() {
+        let settings = FormatSettings::default();
+        assert_eq!(settings.station(1234.567), "12+34.57");
+        assert_eq!(settings.station(1205.), "12+05.00");
+        assert_eq!(settings.station(1299.999), "13+00.00");
+        assert_eq!(settings.station(-50.), "-0+50.00");
+
+        let settings = FormatSettings {
+            station_decimals: 3,
+            station_base: StationBase::Thousand,
+            ..Default::default()
+        };
+        assert_eq!(settings.station(1234.5678), "1+234.568");
+        assert_eq!(settings.station(1005.), "1+005.000");
+
+        let settings = FormatSettings {
+            station_decimals: 0,
+            ..Default::default()
+        };
+        assert_eq!(settings.station(1204.4), "12+04");
+    }
+
+    #[test]
+    fn angles_and_labels() {
+        let angle = Angle::from("10d30'").unwrap();
+        let settings = FormatSettings {
+            angle_format: AngleFormat::DD,
+            label_style: LabelStyle::Long,
+            ..Default::default()
+        };
+        assert_eq!(settings.angle(&angle), "10.5000°");
+        assert_eq!(settings.label(Label::Station), "Station:");
+
+        let settings = FormatSettings {
+            units: Units::Metres,
+            ..Default::default()
+        };
+        assert_eq!(settings.label(Label::Station), "STA:");
+        assert_eq!(settings.label(Label::Elevation), "ELEV (m):");
+        assert_eq!(Units::Metres.station_base(), StationBase::Thousand);
+        assert!(FormatSettings::default()
+            .angle(&angle)
+            .starts_with("DMS: 10d30'"));
+    }
+}
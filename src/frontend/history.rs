@@ -0,0 +1,194 @@
+use std::mem::{discriminant, Discriminant};
+
+use crate::frontend::{Message, RowEdit};
+
+const HISTORY_LIMIT: usize = 200;
+
+/// How a message enters the undo history.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Record {
+    Skip,   // view, selection, tab, export and format messages
+    Typing, // consecutive edits of the same field are a single step
+    Step,
+}
+
+impl Message {
+    pub fn record(&self) -> Record {
+        match self {
+            Message::FontLoaded(_)
+            | Message::FullScreenToggle(_)
+            | Message::SwitchCurveType
+            | Message::RowEdit(RowEdit::Select(_))
+            | Message::FileDialog
+            | Message::Directory(_)
+            | Message::ExportText
+            | Message::ExportPDF
+            | Message::Undo
+            | Message::Redo
+            | Message::TabPressed { .. }
+            | Message::ShortcutsToggle
+            | Message::SettingsToggle
+            | Message::ExportFolderSelect
+            | Message::RecentSelect(_)
+            | Message::ThemeToggle
+            | Message::ScaleToggle
+            | Message::DefaultStandardToggle
+            | Message::TabSelect(_)
+            | Message::TabNew
+            | Message::TabDuplicate
+            | Message::TabClose
+            | Message::TabRename(_)
+            | Message::TabCompareCheck(..)
+            | Message::CompareToggle
+            | Message::StationDecimalsToggle
+            | Message::ElevationDecimalsToggle
+            | Message::LengthDecimalsToggle
+            | Message::StationBaseToggle
+            | Message::AngleFormatToggle
+            | Message::LabelStyleToggle
+            | Message::UnitsToggle => Record::Skip,
+            Message::StationModify(_)
+            | Message::ElevationModify(_)
+            | Message::IncomingGradeModify(_)
+            | Message::OutgoingGradeModify(_)
+            | Message::LengthModify(_)
+            | Message::KValueModify(_)
+            | Message::StationIntervalModify(_)
+            | Message::StationEquationsModify(_)
+            | Message::DesignSpeed(_)
+            | Message::ReactionTimeModify(_)
+            | Message::DecelerationModify(_)
+            | Message::ObstacleStation(_)
+            | Message::ObstacleElevation(_)
+            | Message::RadiusModify(_)
+            | Message::CurveAngleModify(_)
+            | Message::TangentModify(_)
+            | Message::LongChordModify(_)
+            | Message::MiddleOrdinateModify(_)
+            | Message::ExternalModify(_)
+            | Message::DegreeModify(_)
+            | Message::PiPointModify(_)
+            | Message::BackBearingModify(_)
+            | Message::AheadBearingModify(_)
+            | Message::PointModify(_)
+            | Message::OccupiedModify(_)
+            | Message::BacksightModify(_)
+            | Message::LocatePointsModify(_)
+            | Message::MModify(_)
+            | Message::OffsetsModify(_)
+            | Message::TraveledWayModify(_)
+            | Message::LanesModify(_)
+            | Message::PinStation(_) => Record::Typing,
+            _ => Record::Step,
+        }
+    }
+}
+
+/// Undo and redo stacks of snapshots taken before each change.
+#[derive(Debug)]
+pub struct History<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+    typing: Option<Discriminant<Message>>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        History {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            typing: None,
+        }
+    }
+}
+
+impl<T> History<T> {
+    /// Records the state before the message was applied. Typing into the field edited last extends its step.
+    pub fn record(&mut self, before: T, message: &Message) {
+        let record = message.record();
+        if record == Record::Skip {
+            return;
+        }
+        let kind = discriminant(message);
+        let typing = match record {
+            Record::Typing => Some(kind),
+            _ => None,
+        };
+        if typing.is_some() && self.typing == typing {
+            return;
+        }
+
+        self.typing = typing;
+        self.redo.clear();
+        self.undo.push(before);
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    /// State to restore, the current state moves onto the redo stack.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let previous = self.undo.pop()?;
+        self.typing = None;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// State to restore, the current state moves back onto the undo stack.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.typing = None;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod history_tests {
+    use super::{History, Record};
+    use crate::frontend::{Message, RowEdit};
+
+    #[test]
+    fn undo_redo() {
+        let mut history = History::default();
+        let mut state = String::new();
+        // typing "12" then toggling, then typing into another field
+        for (message, next) in [
+            (Message::StationModify("1".into()), "1"),
+            (Message::StationModify("12".into()), "12"),
+            (Message::InputMethodToggle, "12 PVC"),
+            (Message::ExportText, "12 PVC"),
+            (Message::ElevationModify("5".into()), "12 PVC 5"),
+        ] {
+            history.record(state.clone(), &message);
+            state = next.to_string();
+        }
+
+        state = history.undo(state).unwrap();
+        assert_eq!(state, "12 PVC");
+        state = history.undo(state).unwrap();
+        assert_eq!(state, "12");
+        state = history.undo(state).unwrap();
+        assert_eq!(state, "");
+        assert!(history.undo(state.clone()).is_none());
+
+        state = history.redo(state).unwrap();
+        assert_eq!(state, "12");
+        // a new change drops the redo stack
+        history.record(state.clone(), &Message::RemoveObstacle);
+        assert!(!history.can_redo());
+        assert!(history.can_undo());
+
+        assert_eq!(Message::SwitchCurveType.record(), Record::Skip);
+        assert_eq!(Message::RowEdit(RowEdit::Select(0)).record(), Record::Skip);
+        assert_eq!(Message::RowEdit(RowEdit::Delete(0)).record(), Record::Step);
+    }
+}
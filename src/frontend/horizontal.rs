@@ -1,11 +1,17 @@
-use iced::widget::{checkbox, column, text, Column, Row};
+use iced::widget::{checkbox, column, pick_list, text, Column, Row};
 
-use crate::{frontend::*, horizontal::calculate::HorizontalCurve};
+use crate::frontend::*;
+use crate::horizontal::{
+    build::CurveElement,
+    calculate::HorizontalCurve,
+    stakeout::{SetupDefinition, StakeoutMethod},
+    through::PointDefinition,
+};
 
 pub fn horizontal_header_group<'a>() -> Column<'a, Message> {
     let title = text("Horizontal Curves (Pre-Release)")
         .width(Length::Fill)
-        .size(50)
+        .size(TITLE_SIZE)
         .style(Color::from([0.5, 0.5, 0.5]))
         .horizontal_alignment(alignment::Horizontal::Center);
     let switch = button("Switch to Vertical Curve").on_press(Message::SwitchCurveType);
@@ -13,7 +19,7 @@ pub fn horizontal_header_group<'a>() -> Column<'a, Message> {
 }
 
 impl HorizontalData {
-    pub fn horizontal_input_group(&self) -> Column<Message> {
+    pub fn horizontal_input_group(&self) -> Column<'_, Message> {
         let mut column = column![]
             .spacing(10)
             .width(Length::FillPortion(2))
@@ -26,73 +32,375 @@ impl HorizontalData {
         column
     }
 
+    /// Mandatory inputs of the build method paired with their parse errors.
+    fn mandatory_fields(&self) -> Vec<(&str, Option<String>)> {
+        let equations = self.to_equations().unwrap_or_default();
+        let mut fields = vec![(
+            self.input_station.as_str(),
+            station_error(&self.input_station, &equations),
+        )];
+        match self.input_build_method.elements() {
+            Some((first, second)) => {
+                fields.push(self.element_field(first));
+                fields.push(self.element_field(second));
+            }
+            None if self.input_build_method == HorizontalBuildDefinition::ThroughPoint => {
+                fields.push((
+                    &self.input_back_bearing,
+                    field_error(&self.input_back_bearing, coerce_bearing),
+                ));
+                fields.push((
+                    &self.input_ahead_bearing,
+                    field_error(&self.input_ahead_bearing, coerce_bearing),
+                ));
+                fields.push((
+                    &self.input_point,
+                    field_error(&self.input_point, coerce_point),
+                ));
+            }
+            None => {
+                fields.push((
+                    &self.input_degree,
+                    field_error(&self.input_degree, Angle::from),
+                ));
+                fields.push(self.element_field(CurveElement::CurveAngle));
+            }
+        }
+        fields
+    }
+
+    pub fn mandatory_valid(&self) -> bool {
+        fields_valid(&self.mandatory_fields())
+    }
+
+    fn element_field(&self, element: CurveElement) -> (&str, Option<String>) {
+        let value = match element {
+            CurveElement::Radius => &self.input_radius,
+            CurveElement::CurveAngle => &self.input_curve_angle,
+            CurveElement::Tangent => &self.input_tangent,
+            CurveElement::Length => &self.input_length,
+            CurveElement::LongChord => &self.input_long_chord,
+            CurveElement::MiddleOrdinate => &self.input_middle_ordinate,
+            CurveElement::External => &self.input_external,
+        };
+        let error = match element {
+            CurveElement::CurveAngle => field_error(value, Angle::from_directed),
+            _ => field_error(value, coerce_length),
+        };
+        (value, error)
+    }
+
     fn mandatory_block(&self) -> Column<'_, Message> {
+        let equations = self.to_equations().unwrap_or_default();
         column![
             subtitle("Inputs"),
             row![
                 text(format!("{:?} STA:", &self.input_station_method)),
-                text_input("Station (12+34)", &self.input_station).on_input(Message::StationModify),
+                checked_input(
+                    "Station (12+34)",
+                    &self.input_station,
+                    Message::StationModify,
+                    station_error(&self.input_station, &equations)
+                ),
                 button(stext('S')).on_press(Message::StationMethodToggle),
                 button(cycle_icon()).on_press(Message::BuildMethodToggle),
+                button(text(format!("{:?}", self.input_degree_definition)))
+                    .on_press(Message::DegreeDefinitionToggle),
+                button(text(format!("{}", self.input_direction)))
+                    .on_press(Message::DirectionToggle),
             ]
             .spacing(H_S),
-            match self.input_build_method {
-                HorizontalBuildDefinition::RadiusCurveAngle => self.row_radius_curve_angle(),
-                HorizontalBuildDefinition::RadiusTangent => self.row_radius_tangent(),
+            row![
+                text("Build:"),
+                pick_list(
+                    &HorizontalBuildDefinition::ALL[..],
+                    Some(self.input_build_method),
+                    Message::BuildMethodSelect
+                ),
+            ]
+            .spacing(H_S),
+            match self.input_build_method.elements() {
+                Some((first, second)) => column![row![
+                    self.element_input(first).spacing(H_S),
+                    self.element_input(second).spacing(H_S)
+                ]
+                .spacing(H_S)],
+                None if self.input_build_method == HorizontalBuildDefinition::ThroughPoint => {
+                    self.through_point_rows()
+                }
+                None => column![self.row_degree_curve_angle().spacing(H_S)],
             }
             .spacing(H_S),
         ]
         .spacing(H_S)
     }
 
-    fn row_radius_curve_angle(&self) -> Row<'_, Message> {
+    fn element_input(&self, element: CurveElement) -> Row<'_, Message> {
+        let (placeholder, value, message): (_, _, fn(String) -> Message) = match element {
+            CurveElement::Radius => ("(100)", &self.input_radius, Message::RadiusModify),
+            CurveElement::CurveAngle => (
+                "(60d13\'42\")",
+                &self.input_curve_angle,
+                Message::CurveAngleModify,
+            ),
+            CurveElement::Tangent => ("(123)", &self.input_tangent, Message::TangentModify),
+            CurveElement::Length => ("(234)", &self.input_length, Message::LengthModify),
+            CurveElement::LongChord => ("(210)", &self.input_long_chord, Message::LongChordModify),
+            CurveElement::MiddleOrdinate => (
+                "(12)",
+                &self.input_middle_ordinate,
+                Message::MiddleOrdinateModify,
+            ),
+            CurveElement::External => ("(13)", &self.input_external, Message::ExternalModify),
+        };
         row![
-            text("Radius:"),
-            text_input("(100)", &self.input_radius).on_input(Message::RadiusModify),
+            text(format!("{}:", element)),
+            checked_input(placeholder, value, message, self.element_field(element).1)
+        ]
+    }
+
+    fn row_degree_curve_angle(&self) -> Row<'_, Message> {
+        row![
+            text("Degree of Curve:"),
+            checked_input(
+                "(5d43\'46\")",
+                &self.input_degree,
+                Message::DegreeModify,
+                field_error(&self.input_degree, Angle::from)
+            ),
             text("Curve Angle:"),
-            text_input("(60d13\'42\")", &self.input_curve_angle)
-                .on_input(Message::CurveAngleModify)
+            checked_input(
+                "(60d13\'42\")",
+                &self.input_curve_angle,
+                Message::CurveAngleModify,
+                self.element_field(CurveElement::CurveAngle).1
+            )
         ]
     }
 
-    fn row_radius_tangent(&self) -> Row<'_, Message> {
+    fn coordinate_rows(&self) -> Column<'_, Message> {
+        column![
+            row![
+                text("PI:"),
+                checked_input(
+                    "N, E (5000, 5000)",
+                    &self.input_pi_point,
+                    Message::PiPointModify,
+                    field_error(&self.input_pi_point, coerce_point)
+                ),
+            ]
+            .spacing(H_S),
+            row![
+                text("Back Bearing:"),
+                checked_input(
+                    "(N 10d E)",
+                    &self.input_back_bearing,
+                    Message::BackBearingModify,
+                    field_error(&self.input_back_bearing, coerce_bearing)
+                ),
+                text("Ahead Bearing:"),
+                checked_input(
+                    "(50d)",
+                    &self.input_ahead_bearing,
+                    Message::AheadBearingModify,
+                    field_error(&self.input_ahead_bearing, coerce_bearing)
+                ),
+            ]
+            .spacing(H_S),
+        ]
+        .spacing(H_S)
+    }
+
+    fn through_point_rows(&self) -> Column<'_, Message> {
+        let placeholder = match self.input_point_method {
+            PointDefinition::Coordinates => "N, E (5100, 5200)",
+            PointDefinition::DistanceOffset => "Distance, Offset (250, 30)",
+        };
+        column![
+            self.coordinate_rows(),
+            row![
+                text("Point:"),
+                checked_input(
+                    placeholder,
+                    &self.input_point,
+                    Message::PointModify,
+                    field_error(&self.input_point, coerce_point)
+                ),
+                button(text(format!("{:?}", self.input_point_method)))
+                    .on_press(Message::PointMethodToggle),
+            ]
+            .spacing(H_S),
+        ]
+        .spacing(H_S)
+    }
+
+    fn setup_error(&self, value: &str) -> Option<String> {
+        match self.input_setup_method {
+            SetupDefinition::Coordinates => field_error(value, coerce_point),
+            SetupDefinition::StationOffset => field_error(value, |x| {
+                coerce_station_offset(x, &self.to_equations().unwrap_or_default())
+            }),
+        }
+    }
+
+    fn setup_row(&self) -> Row<'_, Message> {
+        let placeholder = match self.input_setup_method {
+            SetupDefinition::Coordinates => ("N, E (5000, 5000)", "N, E (6000, 5000)"),
+            SetupDefinition::StationOffset => ("STA, Offset (12+34, 10)", "STA, Offset (15+00, 0)"),
+        };
         row![
-            text("Radius:"),
-            text_input("(100)", &self.input_radius).on_input(Message::RadiusModify),
-            text("Tangent:"),
-            text_input("(123)", &self.input_tangent).on_input(Message::TangentModify)
+            text("Occupied:"),
+            checked_input(
+                placeholder.0,
+                &self.input_occupied,
+                Message::OccupiedModify,
+                self.setup_error(&self.input_occupied)
+            ),
+            text("Backsight:"),
+            checked_input(
+                placeholder.1,
+                &self.input_backsight,
+                Message::BacksightModify,
+                self.setup_error(&self.input_backsight)
+            ),
+            button(text(format!("{:?}", self.input_setup_method)))
+                .on_press(Message::SetupMethodToggle),
         ]
+        .spacing(H_S)
     }
 
     fn optional_block(&self) -> Column<'_, Message> {
         column![
             subtitle("Additional Details"),
+            format_row(),
+            match self.input_build_method {
+                HorizontalBuildDefinition::ThroughPoint => column![],
+                _ => self.coordinate_rows(),
+            },
             row![
                 text("Interval:"),
-                text_input("(00+25)", &self.input_station_interval)
-                    .on_input(Message::StationIntervalModify),
+                checked_input(
+                    "(00+25)",
+                    &self.input_station_interval,
+                    Message::StationIntervalModify,
+                    field_error(&self.input_station_interval, coerce_station_value)
+                ),
+                button(text(format!("{}", self.input_stakeout_method)))
+                    .on_press(Message::StakeoutMethodToggle),
+            ]
+            .spacing(H_S),
+            row![
+                text("Equations:"),
+                checked_input(
+                    "(125+40.22 = 125+00; 150+00 = 151+00)",
+                    &self.input_station_equations,
+                    Message::StationEquationsModify,
+                    field_error(&self.input_station_equations, coerce_station_equations)
+                ),
+            ]
+            .spacing(H_S),
+            match self.input_stakeout_method {
+                StakeoutMethod::OccupiedPoint => self.setup_row(),
+                _ => row![],
+            },
+            row![
+                text("Locate Points:"),
+                checked_input(
+                    "N, E; N, E (5100, 5200; 4900, 5050)",
+                    &self.input_locate_points,
+                    Message::LocatePointsModify,
+                    field_error(&self.input_locate_points, coerce_points)
+                ),
             ]
             .spacing(H_S),
             row![
                 text("Design Speed:"),
-                text_input("(65)", &self.input_design_speed).on_input(Message::DesignSpeed),
-                text("Clear Distance:"),
-                text_input("(234)", &self.input_m).on_input(Message::MModify),
+                checked_input(
+                    "(65)",
+                    &self.input_design_speed,
+                    Message::DesignSpeed,
+                    field_error(&self.input_design_speed, coerce_speed)
+                ),
+                text("Clear Distance (HSO):"),
+                checked_input(
+                    "(234)",
+                    &self.input_m,
+                    Message::MModify,
+                    field_error(&self.input_m, coerce_length)
+                ),
+                button(stext('R')).on_press(Message::SpeedLookupToggle),
                 button(stext('A')).on_press(Message::DesignStandardToggle),
                 button(cycle_icon()).on_press(Message::SightTypeToggle),
+                button(exit_icon()).on_press(Message::LoadStandard),
             ]
             .spacing(H_S),
+            maneuver_row(self.input_sight_type),
+            error_row(&self.standard_error),
             checkbox(
                 "Sustained Downgrade",
                 self.sustained_downgrade,
                 Message::SustainedDowngradeCheck,
             )
             .spacing(H_S),
+            row![
+                text("Offsets:"),
+                checked_input(
+                    "(-12, 12)",
+                    &self.input_offsets,
+                    Message::OffsetsModify,
+                    field_error(&self.input_offsets, coerce_offsets)
+                ),
+            ]
+            .spacing(H_S),
+            row![
+                text("Pins:"),
+                checked_input(
+                    "STA:",
+                    &self.input_pin_station,
+                    Message::PinStation,
+                    station_error(
+                        &self.input_pin_station,
+                        &self.to_equations().unwrap_or_default()
+                    )
+                ),
+                button(match self.selected_pin {
+                    Some(_) => good_check_icon(),
+                    None => stext('+'),
+                })
+                .on_press(Message::AddPin),
+                button(stext('-')).on_press(Message::RemovePin),
+                button(exit_icon()).on_press(Message::ImportRows),
+            ]
+            .spacing(H_S),
+            error_row(&self.list_error),
+            table(
+                self.pin.interval.iter().map(|x| x.to_string()).collect(),
+                self.selected_pin
+            ),
+            row![
+                text("Traveled Way:"),
+                checked_input(
+                    "(24)",
+                    &self.input_traveled_way,
+                    Message::TraveledWayModify,
+                    field_error(&self.input_traveled_way, coerce_length)
+                ),
+                text("Lanes:"),
+                checked_input(
+                    "(2)",
+                    &self.input_lanes,
+                    Message::LanesModify,
+                    field_error(&self.input_lanes, coerce_lanes)
+                ),
+                button(text(format!("{:?}", self.input_design_vehicle)))
+                    .on_press(Message::DesignVehicleToggle),
+            ]
+            .spacing(H_S),
         ]
         .spacing(H_S)
     }
 
-    pub fn horizontal_output_group(&self) -> Column<Message> {
+    pub fn horizontal_output_group(&self) -> Column<'_, Message> {
         let h_s = 5;
         let mut column = column![]
             .spacing(10)
@@ -108,23 +416,66 @@ impl HorizontalData {
                     .push(self.curve_details_block(&w))
                     .push(self.major_stations_block(&w))
                     .push(self.validation_block(&w))
+                    .push(self.widening_block(&w))
+                    .push(self.offset_block(&w))
+                    .push(self.locate_block(&w))
                     .push(self.interval_block(&w));
             }
         }
         column
     }
 
+    /// Key dimensions and compliance results for the comparison view.
+    pub fn comparison_rows(&self) -> Result<Vec<(&'static str, String)>> {
+        let w = self.to_horizontal_curve()?;
+        let dimensions = w.dimensions;
+        let settings = format_settings();
+        let mut rows = vec![
+            ("PI", w.stations.pi.to_input()),
+            ("Length", fmt_length(dimensions.curve_length)),
+            ("Radius", fmt_length(dimensions.radius)),
+            (
+                "Curve Angle",
+                format!(
+                    "{} {}",
+                    settings.angle(&dimensions.curve_angle),
+                    dimensions.direction
+                ),
+            ),
+            ("Tangent", fmt_length(dimensions.tangent)),
+            ("External", fmt_length(dimensions.external)),
+        ];
+        let check = match self.input_design_speed.is_empty() || self.input_m.is_empty() {
+            true => "No speed or clear distance given.".to_string(),
+            false => compliance_text(
+                w.is_compliant(
+                    self.input_design_standard,
+                    self.input_sight_type,
+                    self.input_speed_lookup,
+                    calc_adjustment(self.sustained_downgrade),
+                ),
+                dimensions.sight_distance,
+            ),
+        };
+        rows.push(("Sight Distance Check", check));
+        Ok(rows)
+    }
+
     fn curve_details_block(&self, w: &HorizontalCurve) -> Column<'_, Message> {
         column![subtitle("Curve Details"), text(format!("{}", w.dimensions)),]
     }
 
     fn major_stations_block(&self, w: &HorizontalCurve) -> Column<'_, Message> {
-        column![subtitle("Major Stations"), text(format!("{}", w.stations)),]
+        let mut major_column = column![subtitle("Major Stations"), text(format!("{}", w.stations))];
+        if let Ok(points) = w.major_points() {
+            major_column = major_column.push(text(format!("{}", points)));
+        }
+        major_column
     }
 
     fn validation_block(&self, w: &HorizontalCurve) -> Column<'_, Message> {
         let mut validation_column = column![subtitle(&format!(
-            "Sight Distance ( {:?} - {:?} )",
+            "Sight Distance ( {} - {:?} )",
             self.input_design_standard, self.input_sight_type
         ))];
         if self.input_design_speed.is_empty() || self.input_m.is_empty() {
@@ -136,6 +487,7 @@ impl HorizontalData {
             match w.is_compliant(
                 self.input_design_standard,
                 self.input_sight_type,
+                self.input_speed_lookup,
                 calc_adjustment(self.sustained_downgrade),
             ) {
                 Err(e) => {
@@ -146,14 +498,25 @@ impl HorizontalData {
                     if j.0 {
                         validation_column = validation_column.push(row![
                             good_check_icon(),
-                            text(format!(" {:.2} > {:.2}", w.dimensions.sight_distance, j.1))
+                            text(format!(
+                                " {} > {}",
+                                fmt_length(w.dimensions.sight_distance),
+                                fmt_length(j.1)
+                            ))
                         ]);
                     } else {
                         validation_column = validation_column.push(row![
                             exclam_icon(),
-                            text(format!(" {:.2} < {:.2}", w.dimensions.sight_distance, j.1))
+                            text(format!(
+                                " {} < {}",
+                                fmt_length(w.dimensions.sight_distance),
+                                fmt_length(j.1)
+                            ))
                         ]);
                     }
+                    validation_column = validation_column.push(text(format!(" {}", j.2)));
+                    validation_column =
+                        validation_column.push(text(format!("{}", w.hso_detail(j.1))));
                 }
             }
         }
@@ -161,8 +524,102 @@ impl HorizontalData {
         validation_column
     }
 
+    fn widening_block(&self, w: &HorizontalCurve) -> Column<'_, Message> {
+        let mut widening_column = column![subtitle(&format!(
+            "Traveled Way Widening ( {:?} )",
+            self.input_design_vehicle
+        ))];
+
+        if self.input_traveled_way.is_empty() {
+            widening_column =
+                widening_column.push(row![notification_icon(), text(" No traveled way given.")]);
+        } else {
+            match self.to_widening(w) {
+                Err(e) => {
+                    widening_column =
+                        widening_column.push(row![exclam_icon(), text(format!(" {}", e))]);
+                }
+                Ok(j) => {
+                    widening_column = widening_column.push(text(format!("{}", j)));
+                }
+            };
+        }
+
+        widening_column
+    }
+
+    fn offset_block(&self, w: &HorizontalCurve) -> Column<'_, Message> {
+        let mut offset_column = column![subtitle("Offset Curves")];
+
+        if self.input_offsets.is_empty() {
+            offset_column =
+                offset_column.push(row![notification_icon(), text(" No offsets given.")]);
+            return offset_column;
+        }
+
+        match coerce_offsets(&self.input_offsets) {
+            Err(e) => {
+                offset_column = offset_column.push(row![exclam_icon(), text(format!(" {}", e))]);
+            }
+            Ok(offsets) => {
+                let interval = coerce_station_value(&self.input_station_interval);
+                for offset in offsets {
+                    match w.offset_curve(offset) {
+                        Err(e) => {
+                            offset_column = offset_column.push(row![
+                                exclam_icon(),
+                                text(format!(" {}: {}", fmt_length(offset), e))
+                            ]);
+                        }
+                        Ok(offset_curve) => {
+                            offset_column = offset_column.push(text(format!("{}", offset_curve)));
+                            if let Ok(t) = interval {
+                                offset_column = offset_column.push(text(format!(
+                                    "Radial to centerline stations\n{}",
+                                    w.radial_interval_stations(&offset_curve, t)
+                                )));
+                            }
+                        }
+                    }
+                }
+            }
+        };
+
+        offset_column
+    }
+
+    fn locate_block(&self, w: &HorizontalCurve) -> Column<'_, Message> {
+        let mut locate_column = column![subtitle("Point Locations")];
+
+        if self.input_locate_points.is_empty() {
+            locate_column =
+                locate_column.push(row![notification_icon(), text(" No points given.")]);
+            return locate_column;
+        }
+
+        match coerce_points(&self.input_locate_points) {
+            Err(e) => {
+                locate_column = locate_column.push(row![exclam_icon(), text(format!(" {}", e))]);
+            }
+            Ok(points) => {
+                for point in points {
+                    locate_column = match w.locate_point(point) {
+                        Err(e) => locate_column
+                            .push(row![exclam_icon(), text(format!(" {} > {}", point, e))]),
+                        Ok(location) => locate_column.push(text(format!("> {}", location))),
+                    };
+                }
+            }
+        };
+
+        locate_column
+    }
+
     fn interval_block(&self, w: &HorizontalCurve) -> Column<'_, Message> {
-        let mut interval_column = column![subtitle("Interval Stations")];
+        let mut interval_column = column![subtitle(&format!(
+            "Interval Stations ( {} )",
+            self.input_stakeout_method
+        ))];
 
         if self.input_station_interval.is_empty() {
             interval_column =
@@ -173,10 +630,15 @@ impl HorizontalData {
                     interval_column =
                         interval_column.push(row![exclam_icon(), text(format!(" {}", e))]);
                 }
-                Ok(t) => {
-                    interval_column =
-                        interval_column.push(text(format!("{}", w.interval_stations(t))));
-                }
+                Ok(t) => match self.to_stakeout(w, t) {
+                    Err(e) => {
+                        interval_column =
+                            interval_column.push(row![exclam_icon(), text(format!(" {}", e))]);
+                    }
+                    Ok(detail) => {
+                        interval_column = interval_column.push(text(format!("{}", detail)));
+                    }
+                },
             };
         }
 
@@ -1,34 +1,78 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use iced::{
     alignment::{self},
-    event,
     font::{self, Font},
-    keyboard::{self, KeyCode, Modifiers},
     subscription,
     theme::Theme,
     widget::{
-        button, column, container, row, scrollable, text, text_input, Column, Row, Rule, Text,
+        button, column, container, focus_next, focus_previous, pick_list, row, scrollable, text,
+        text_input, Column, Row, Rule, Text,
     },
     window::{self, Mode},
-    Application, Color, Command, Element, Event, Length, Subscription,
+    Application, Color, Command, Element, Length, Subscription,
 };
-use once_cell::sync::Lazy;
+use std::path::Path;
 
+pub mod history;
 pub mod horizontal;
+pub mod project;
+pub mod settings;
+pub mod shortcuts;
+pub mod table;
+pub mod tabs;
+pub mod validation;
 pub mod vertical;
 
 use crate::datatypes::*;
 use crate::export::*;
+use crate::format::*;
 use crate::horizontal::*;
+use crate::tables::load_standard;
 use crate::vertical::*;
 
-static INPUT_ID: Lazy<text_input::Id> = Lazy::new(text_input::Id::unique);
+use self::history::*;
+use self::settings::*;
+use self::shortcuts::*;
+use self::table::*;
+use self::tabs::*;
+use self::validation::*;
+
 const H_S: u16 = 7;
 
 #[derive(Debug)]
-pub enum CurveSolver {
-    Vertical(VerticalData),
-    Horizontal(HorizontalData),
+pub struct CurveSolver {
+    tabs: Tabs,
+    comparing: bool,
+    shortcuts: bool,
+    settings: UserSettings,
+    settings_open: bool,
+    settings_error: String,
+    settings_writable: bool, // false when the settings file couldn't be read, so it isn't overwritten
+    status: Result<String, String>, // last export or opened project
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CurveType {
+    #[default]
+    Vertical,
+    Horizontal,
+}
+
+impl CurveType {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Vertical => Self::Horizontal,
+            Self::Horizontal => Self::Vertical,
+        }
+    }
+}
+
+/// Vertical and horizontal design of one alignment. Switching the view keeps both.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Project {
+    pub vertical: VerticalData,
+    pub horizontal: HorizontalData,
+    pub view: CurveType,
 }
 
 #[derive(Debug, Clone)]
@@ -42,6 +86,24 @@ pub enum Message {
     Directory(String),
     ExportText,
     ExportPDF,
+    Undo,
+    Redo,
+    TabPressed { shift: bool },
+    ShortcutsToggle,
+    SettingsToggle,
+    ExportFolderSelect,
+    RecentSelect(String),
+    ThemeToggle,
+    ScaleToggle,
+    DefaultStandardToggle,
+    // Tabs
+    TabSelect(usize),
+    TabNew,
+    TabDuplicate,
+    TabClose,
+    TabRename(String),
+    TabCompareCheck(usize, bool),
+    CompareToggle,
     // Vertical
     InputMethodToggle,
     StationModify(String),
@@ -49,25 +111,63 @@ pub enum Message {
     IncomingGradeModify(String),
     OutgoingGradeModify(String),
     LengthModify(String),
+    LengthMethodToggle,
+    KValueModify(String),
     StationIntervalModify(String),
+    StationEquationsModify(String),
+    StationDecimalsToggle,
+    ElevationDecimalsToggle,
+    LengthDecimalsToggle,
+    StationBaseToggle,
+    AngleFormatToggle,
+    LabelStyleToggle,
+    UnitsToggle,
     DesignStandardToggle,
+    LoadStandard,
     SightTypeToggle,
+    ManeuverToggle,
+    SpeedLookupToggle,
     DesignSpeed(String),
+    ReactionTimeModify(String),
+    DecelerationModify(String),
     ObstacleStation(String),
     ObstacleElevation(String),
     ObstacleTypeToggle,
     AddObstacle,
     RemoveObstacle,
+    RowEdit(RowEdit),
+    ImportRows,
     // Horizontal
     StationMethodToggle,
     BuildMethodToggle,
+    BuildMethodSelect(HorizontalBuildDefinition),
     RadiusModify(String),
     CurveAngleModify(String),
     TangentModify(String),
+    LongChordModify(String),
+    MiddleOrdinateModify(String),
+    ExternalModify(String),
+    DegreeModify(String),
+    DegreeDefinitionToggle,
+    DirectionToggle,
+    PiPointModify(String),
+    BackBearingModify(String),
+    AheadBearingModify(String),
+    PointModify(String),
+    PointMethodToggle,
+    StakeoutMethodToggle,
+    SetupMethodToggle,
+    OccupiedModify(String),
+    BacksightModify(String),
+    LocatePointsModify(String),
     MModify(String),
-    // PinStation(String),
-    // AddPin,
-    // RemovePin,
+    OffsetsModify(String),
+    TraveledWayModify(String),
+    LanesModify(String),
+    DesignVehicleToggle,
+    PinStation(String),
+    AddPin,
+    RemovePin,
 }
 
 impl Application for CurveSolver {
@@ -77,8 +177,31 @@ impl Application for CurveSolver {
     type Flags = ();
 
     fn new(_flags: ()) -> (CurveSolver, Command<Message>) {
+        let (settings, settings_error, settings_writable) = match UserSettings::load() {
+            Ok((settings, errors)) => {
+                let errors = errors.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+                (settings, errors.join("\n"), true)
+            }
+            Err(e) => (
+                UserSettings::default(),
+                format!("Settings couldn't be read, changes won't be saved: {}", e),
+                false,
+            ),
+        };
+        set_format_settings(settings.format);
+
         (
-            CurveSolver::Vertical(VerticalData::default()),
+            CurveSolver {
+                tabs: Tabs::new(settings.design_standard),
+                comparing: false,
+                shortcuts: false,
+                settings,
+                // skipped lines are shown in the settings panel
+                settings_open: !settings_error.is_empty(),
+                settings_error,
+                settings_writable,
+                status: Ok(String::new()),
+            },
             Command::batch([
                 font::load(include_bytes!("../../fonts/Arrows.ttf").as_slice())
                     .map(Message::FontLoaded),
@@ -92,28 +215,135 @@ impl Application for CurveSolver {
         String::from("Zi's Curve Solver")
     }
 
+    fn theme(&self) -> Theme {
+        self.settings.theme.to_theme()
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.settings.scale
+    }
+
     fn update(&mut self, message: Message) -> Command<Message> {
+        match &message {
+            Message::TabPressed { shift: true } => return focus_previous(),
+            Message::TabPressed { shift: false } => return focus_next(),
+            Message::ShortcutsToggle => self.shortcuts = !self.shortcuts,
+            Message::SettingsToggle => self.settings_open = !self.settings_open,
+            Message::FileDialog => {
+                let folder = self.settings.export_folder();
+                let tab = self.tabs.current_mut();
+                let filename = tab.project.file_name(&tab.name).unwrap_or_default();
+                tab.project.set_directory(save_to(&folder, &filename));
+                return Command::none();
+            }
+            Message::ExportText => {
+                self.export(false);
+                return Command::none();
+            }
+            Message::ExportPDF => {
+                self.export(true);
+                return Command::none();
+            }
+            Message::ExportFolderSelect => {
+                let folder = folder_from();
+                if !folder.is_empty() {
+                    self.modify_settings(|x| x.export_folder = folder);
+                }
+            }
+            Message::RecentSelect(path) => self.open_recent(path),
+            Message::ThemeToggle => self.modify_settings(|x| x.theme = x.theme.next()),
+            Message::ScaleToggle => self.modify_settings(|x| x.scale = next_scale(x.scale)),
+            Message::DefaultStandardToggle => self
+                .modify_settings(|x| x.design_standard = next_default_standard(x.design_standard)),
+            Message::StationDecimalsToggle => self.modify_settings(|x| {
+                x.format.station_decimals = next_decimals(x.format.station_decimals)
+            }),
+            Message::ElevationDecimalsToggle => self.modify_settings(|x| {
+                x.format.elevation_decimals = next_decimals(x.format.elevation_decimals)
+            }),
+            Message::LengthDecimalsToggle => self.modify_settings(|x| {
+                x.format.length_decimals = next_decimals(x.format.length_decimals)
+            }),
+            Message::StationBaseToggle => {
+                self.modify_settings(|x| x.format.station_base = x.format.station_base.next())
+            }
+            Message::AngleFormatToggle => {
+                self.modify_settings(|x| x.format.angle_format = x.format.angle_format.next())
+            }
+            Message::LabelStyleToggle => {
+                self.modify_settings(|x| x.format.label_style = x.format.label_style.next())
+            }
+            // the station base follows the units, it can still be changed afterwards
+            Message::UnitsToggle => self.modify_settings(|x| {
+                x.format.units = x.format.units.next();
+                x.format.station_base = x.format.units.station_base();
+            }),
+            Message::TabSelect(i) => {
+                self.tabs.select(*i);
+                self.comparing = false;
+            }
+            Message::TabNew => self.tabs.add(),
+            Message::TabDuplicate => self.tabs.duplicate(),
+            Message::TabClose => self.tabs.close(),
+            Message::TabRename(raw_input) => self.tabs.rename(raw_input.clone()),
+            Message::TabCompareCheck(i, compare) => self.tabs.set_compare(*i, *compare),
+            Message::CompareToggle => self.comparing = !self.comparing,
+            _ => (),
+        }
+        let tab = self.tabs.current_mut();
+
+        let (before, recorded) = (tab.project.clone(), message.clone());
+        let restored = match message {
+            Message::Undo => tab.history.undo(before.clone()),
+            Message::Redo => tab.history.redo(before.clone()),
+            _ => None,
+        };
+        // the export folder isn't an input, it survives undo and redo
+        if let Some(mut project) = restored {
+            project.set_directory(tab.project.directory().to_string());
+            tab.project = project;
+        }
+
         let generic = match message {
             Message::FullScreenToggle(mode) => window::change_mode(mode),
             Message::SwitchCurveType => {
-                self.next_page();
+                tab.project.next_page();
+                Command::none()
+            }
+            Message::AddObstacle | Message::AddPin => {
+                let result = tab.project.add_to_list();
+                tab.project.set_list_error(result);
+                Command::none()
+            }
+            Message::RemoveObstacle | Message::RemovePin => {
+                tab.project.remove_from_list();
+                Command::none()
+            }
+            Message::RowEdit(edit) => {
+                tab.project.edit_list(edit);
+                Command::none()
+            }
+            Message::ImportRows => {
+                let path = open_from();
+                if !path.is_empty() {
+                    let result = tab.project.import_list(&path);
+                    tab.project.set_list_error(result);
+                }
                 Command::none()
             }
             _ => Command::none(),
         };
 
-        match self {
-            CurveSolver::Vertical(vertical_data) => {
+        let command = match tab.project.view {
+            CurveType::Vertical => {
+                let vertical_data = &mut tab.project.vertical;
                 vertical_data.success_flags = [ExportSuccess::None; 2];
 
                 match message {
-                    Message::FileDialog => {
-                        vertical_data.input_directory = save_to();
-                    }
                     // Message::Directory(raw_data) => { // Do not edit input directly, will crash native_dialog.
                     //     vertical_data.input_directory = raw_data;
                     // }
-                    Message::InputMethodToggle => {
+                    Message::InputMethodToggle | Message::StationMethodToggle => {
                         vertical_data.input_method = vertical_data.input_method.next();
                     }
                     Message::StationModify(raw_input) => {
@@ -131,19 +361,58 @@ impl Application for CurveSolver {
                     Message::LengthModify(raw_input) => {
                         vertical_data.input_length = raw_input;
                     }
+                    Message::LengthMethodToggle => {
+                        vertical_data.input_length_method =
+                            vertical_data.input_length_method.next();
+                    }
+                    Message::KValueModify(raw_input) => {
+                        vertical_data.input_k_value = raw_input;
+                    }
                     Message::StationIntervalModify(raw_input) => {
                         vertical_data.input_station_interval = raw_input;
                     }
+                    Message::StationEquationsModify(raw_input) => {
+                        vertical_data.input_station_equations = raw_input;
+                        let equations = vertical_data.to_equations().unwrap_or_default();
+                        for (station, _) in vertical_data.obstacles.interval.iter_mut() {
+                            *station = equations.tag(*station);
+                        }
+                    }
                     Message::DesignStandardToggle => {
                         vertical_data.input_design_standard =
                             vertical_data.input_design_standard.next();
                     }
+                    Message::LoadStandard => {
+                        let path = open_from();
+                        if !path.is_empty() {
+                            match load_standard(&path) {
+                                Ok(w) => {
+                                    vertical_data.input_design_standard = w;
+                                    vertical_data.standard_error.clear();
+                                }
+                                Err(e) => vertical_data.standard_error = format!("{}", e),
+                            }
+                        }
+                    }
                     Message::SightTypeToggle => {
                         vertical_data.input_sight_type = vertical_data.input_sight_type.next();
                     }
+                    Message::ManeuverToggle => {
+                        vertical_data.input_sight_type =
+                            vertical_data.input_sight_type.next_maneuver();
+                    }
+                    Message::SpeedLookupToggle => {
+                        vertical_data.input_speed_lookup = vertical_data.input_speed_lookup.next();
+                    }
                     Message::DesignSpeed(raw_input) => {
                         vertical_data.input_design_speed = raw_input;
                     }
+                    Message::ReactionTimeModify(raw_input) => {
+                        vertical_data.input_reaction_time = raw_input;
+                    }
+                    Message::DecelerationModify(raw_input) => {
+                        vertical_data.input_deceleration = raw_input;
+                    }
                     Message::SustainedDowngradeCheck(raw_input) => {
                         vertical_data.sustained_downgrade = raw_input;
                     }
@@ -157,37 +426,15 @@ impl Application for CurveSolver {
                         vertical_data.input_obstacle_type =
                             vertical_data.input_obstacle_type.next();
                     }
-                    Message::AddObstacle => {
-                        let _ = self.add_to_list();
-                    }
-                    Message::RemoveObstacle => {
-                        vertical_data.obstacles.interval.pop();
-                    }
-                    Message::ExportText => match vertical_data.export_txt() {
-                        Ok(w) => vertical_data.success_flags[0] = ExportSuccess::Success,
-                        Err(e) => {
-                            vertical_data.input_directory = format!("{:?}", e);
-                            vertical_data.success_flags[0] = ExportSuccess::Failure;
-                        }
-                    },
-                    Message::ExportPDF => match vertical_data.export_pdf() {
-                        Ok(w) => vertical_data.success_flags[1] = ExportSuccess::Success,
-                        Err(e) => {
-                            vertical_data.input_directory = format!("{:?}", e);
-                            vertical_data.success_flags[1] = ExportSuccess::Failure;
-                        }
-                    },
                     _ => (),
                 };
                 Command::batch(vec![generic])
             }
-            CurveSolver::Horizontal(horizontal_data) => {
+            CurveType::Horizontal => {
+                let horizontal_data = &mut tab.project.horizontal;
                 horizontal_data.success_flags = [ExportSuccess::None; 2];
 
                 match message {
-                    Message::FileDialog => {
-                        horizontal_data.input_directory = save_to();
-                    }
                     // Message::Directory(raw_data) => {
                     //     horizontal_data.input_directory = raw_data;
                     // }
@@ -195,13 +442,36 @@ impl Application for CurveSolver {
                         horizontal_data.input_build_method =
                             horizontal_data.input_build_method.next();
                     }
+                    Message::BuildMethodSelect(raw_data) => {
+                        horizontal_data.input_build_method = raw_data;
+                    }
                     Message::DesignStandardToggle => {
                         horizontal_data.input_design_standard =
                             horizontal_data.input_design_standard.next();
                     }
+                    Message::LoadStandard => {
+                        let path = open_from();
+                        if !path.is_empty() {
+                            match load_standard(&path) {
+                                Ok(w) => {
+                                    horizontal_data.input_design_standard = w;
+                                    horizontal_data.standard_error.clear();
+                                }
+                                Err(e) => horizontal_data.standard_error = format!("{}", e),
+                            }
+                        }
+                    }
                     Message::SightTypeToggle => {
                         horizontal_data.input_sight_type = horizontal_data.input_sight_type.next();
                     }
+                    Message::ManeuverToggle => {
+                        horizontal_data.input_sight_type =
+                            horizontal_data.input_sight_type.next_maneuver();
+                    }
+                    Message::SpeedLookupToggle => {
+                        horizontal_data.input_speed_lookup =
+                            horizontal_data.input_speed_lookup.next();
+                    }
                     Message::StationMethodToggle => {
                         horizontal_data.input_station_method =
                             horizontal_data.input_station_method.next();
@@ -221,55 +491,144 @@ impl Application for CurveSolver {
                     Message::TangentModify(raw_data) => {
                         horizontal_data.input_tangent = raw_data;
                     }
+                    Message::LongChordModify(raw_data) => {
+                        horizontal_data.input_long_chord = raw_data;
+                    }
+                    Message::MiddleOrdinateModify(raw_data) => {
+                        horizontal_data.input_middle_ordinate = raw_data;
+                    }
+                    Message::ExternalModify(raw_data) => {
+                        horizontal_data.input_external = raw_data;
+                    }
+                    Message::DegreeModify(raw_data) => {
+                        horizontal_data.input_degree = raw_data;
+                    }
+                    Message::DegreeDefinitionToggle => {
+                        horizontal_data.input_degree_definition =
+                            horizontal_data.input_degree_definition.next();
+                    }
+                    Message::DirectionToggle => {
+                        horizontal_data.input_direction = horizontal_data.input_direction.next();
+                    }
+                    Message::PiPointModify(raw_data) => {
+                        horizontal_data.input_pi_point = raw_data;
+                    }
+                    Message::BackBearingModify(raw_data) => {
+                        horizontal_data.input_back_bearing = raw_data;
+                    }
+                    Message::AheadBearingModify(raw_data) => {
+                        horizontal_data.input_ahead_bearing = raw_data;
+                    }
+                    Message::PointModify(raw_data) => {
+                        horizontal_data.input_point = raw_data;
+                    }
+                    Message::PointMethodToggle => {
+                        horizontal_data.input_point_method =
+                            horizontal_data.input_point_method.next();
+                    }
+                    Message::StakeoutMethodToggle => {
+                        horizontal_data.input_stakeout_method =
+                            horizontal_data.input_stakeout_method.next();
+                    }
+                    Message::SetupMethodToggle => {
+                        horizontal_data.input_setup_method =
+                            horizontal_data.input_setup_method.next();
+                    }
+                    Message::OccupiedModify(raw_data) => {
+                        horizontal_data.input_occupied = raw_data;
+                    }
+                    Message::BacksightModify(raw_data) => {
+                        horizontal_data.input_backsight = raw_data;
+                    }
+                    Message::LocatePointsModify(raw_data) => {
+                        horizontal_data.input_locate_points = raw_data;
+                    }
                     Message::StationIntervalModify(raw_data) => {
                         horizontal_data.input_station_interval = raw_data;
                     }
+                    Message::StationEquationsModify(raw_data) => {
+                        horizontal_data.input_station_equations = raw_data;
+                        let equations = horizontal_data.to_equations().unwrap_or_default();
+                        for station in horizontal_data.pin.interval.iter_mut() {
+                            *station = equations.tag(*station);
+                        }
+                    }
                     Message::StationModify(raw_data) => {
                         horizontal_data.input_station = raw_data;
                     }
                     Message::MModify(raw_data) => {
                         horizontal_data.input_m = raw_data;
                     }
+                    Message::OffsetsModify(raw_data) => {
+                        horizontal_data.input_offsets = raw_data;
+                    }
+                    Message::TraveledWayModify(raw_data) => {
+                        horizontal_data.input_traveled_way = raw_data;
+                    }
+                    Message::LanesModify(raw_data) => {
+                        horizontal_data.input_lanes = raw_data;
+                    }
+                    Message::DesignVehicleToggle => {
+                        horizontal_data.input_design_vehicle =
+                            horizontal_data.input_design_vehicle.next();
+                    }
                     Message::SustainedDowngradeCheck(raw_input) => {
                         horizontal_data.sustained_downgrade = raw_input;
                     }
-                    Message::ExportText => match horizontal_data.export_txt() {
-                        Ok(w) => horizontal_data.success_flags[0] = ExportSuccess::Success,
-                        Err(e) => horizontal_data.success_flags[0] = ExportSuccess::Failure,
-                    },
-                    Message::ExportPDF => match horizontal_data.export_pdf() {
-                        Ok(w) => horizontal_data.success_flags[1] = ExportSuccess::Success,
-                        Err(e) => horizontal_data.success_flags[1] = ExportSuccess::Failure,
-                    },
-                    // Message::PinStation(raw_data) => {
-                    //     horizontal_data.input_pin_station = raw_data;
-                    //
-                    // }
-                    // Message::AddPin => {
-                    //     let _ = self.add_to_list();
-                    //
-                    // }
-                    // Message::RemovePin => {
-                    //     horizontal_data.pin.interval.pop();
-                    //
-                    // }
+                    Message::PinStation(raw_data) => {
+                        horizontal_data.input_pin_station = raw_data;
+                    }
                     _ => (),
                 };
                 Command::batch(vec![generic])
             }
+        };
+        // only changes to the design are worth an undo step
+        if before.design() != tab.project.design() {
+            tab.history.record(before, &recorded);
         }
+        command
     }
 
-    fn view(&self) -> Element<Message> {
-        let body = match self {
-            CurveSolver::Vertical(vertical_data) => {
+    fn view(&self) -> Element<'_, Message> {
+        let tab_row = self.tabs.tab_row(self.comparing);
+        let mut sheet = match self.shortcuts {
+            true => shortcut_sheet(),
+            false => column![],
+        };
+        if self.settings_open {
+            sheet = sheet.push(self.settings.settings_block(&self.settings_error));
+        }
+        if self.comparing {
+            return scrollable(
+                container(
+                    column![
+                        title_header("Curve Comparison"),
+                        sheet,
+                        tab_row,
+                        self.tabs.comparison()
+                    ]
+                    .spacing(H_S),
+                )
+                .width(Length::Fill)
+                .padding(40)
+                .center_x(),
+            )
+            .into();
+        }
+
+        let project = &self.tabs.current().project;
+        let body = match project.view {
+            CurveType::Vertical => {
+                let vertical_data = &project.vertical;
                 row![
                     vertical_data.vertical_input_group(),
                     Rule::vertical(40),
                     vertical_data.vertical_output_group()
                 ]
             }
-            CurveSolver::Horizontal(horizontal_data) => {
+            CurveType::Horizontal => {
+                let horizontal_data = &project.horizontal;
                 row![
                     horizontal_data.horizontal_input_group(),
                     Rule::vertical(40),
@@ -279,7 +638,7 @@ impl Application for CurveSolver {
         };
 
         scrollable(
-            container(column![self.task_row(), body].spacing(H_S))
+            container(column![self.task_row(), sheet, tab_row, body].spacing(H_S))
                 .width(Length::Fill)
                 .padding(40)
                 .center_x(),
@@ -288,129 +647,371 @@ impl Application for CurveSolver {
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        subscription::events_with(|event, status| match (event, status) {
-            // (
-            //     Event::Keyboard(keyboard::Event::KeyPressed {
-            //         key_code: keyboard::KeyCode::Tab,
-            //         modifiers,
-            //         ..
-            //     }),
-            //     event::Status::Ignored,
-            // ) => Some(Message::TabPressed {
-            //     shift: modifiers.shift(),
-            // }),
-            (
-                Event::Keyboard(keyboard::Event::KeyPressed {
-                    key_code,
-                    modifiers: Modifiers::SHIFT,
-                }),
-                event::Status::Ignored,
-            ) => match key_code {
-                KeyCode::Up => Some(Message::FullScreenToggle(Mode::Fullscreen)),
-                KeyCode::Down => Some(Message::FullScreenToggle(Mode::Windowed)),
-                _ => None,
-            },
-            _ => None,
-        })
+        subscription::events_with(key_event)
     }
 }
 
 impl CurveSolver {
+    /// Exports the current curve and reports the result in the status row.
+    fn export(&mut self, pdf: bool) {
+        let folder = self.settings.export_folder();
+        let tab = self.tabs.current_mut();
+        let extension = match pdf {
+            true => "pdf",
+            false => "txt",
+        };
+
+        match tab.project.export(&tab.name, &folder, pdf) {
+            Ok(path) => {
+                self.status = Ok(format!("Exported {}.{}", path, extension));
+                let parent = Path::new(&path)
+                    .parent()
+                    .map(|x| x.to_string_lossy().to_string());
+                self.modify_settings(|x| {
+                    x.add_recent(path);
+                    if let Some(parent) = parent.filter(|x| !x.is_empty()) {
+                        x.export_folder = parent;
+                    }
+                });
+            }
+            Err(e) => self.status = Err(format!("Export failed: {}", e)),
+        }
+    }
+
+    /// Opens the project saved with a recent export in a tab, or selects the tab already exporting there.
+    fn open_recent(&mut self, path: &str) {
+        self.comparing = false;
+        if self.tabs.select_directory(path) {
+            return;
+        }
+        self.status = match Project::open(path) {
+            Ok((project, errors)) => {
+                let name = Path::new(path)
+                    .file_name()
+                    .map(|x| x.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.tabs.open(name, project);
+                match errors.len() {
+                    0 => Ok(format!("Opened {}", path)),
+                    n => Ok(format!("Opened {}, {} unreadable lines skipped", path, n)),
+                }
+            }
+            Err(e) => Err(format!("Open failed: {}", e)),
+        };
+    }
+
+    /// Applies a settings change and saves it for the next session.
+    fn modify_settings(&mut self, modify: impl FnOnce(&mut UserSettings)) {
+        modify(&mut self.settings);
+        set_format_settings(self.settings.format);
+        self.tabs
+            .set_default_standard(self.settings.design_standard);
+        if !self.settings_writable {
+            return;
+        }
+        self.settings_error = match self.settings.save() {
+            Ok(_) => String::new(),
+            Err(e) => e.to_string(),
+        };
+    }
+
     fn task_row(&self) -> Column<'_, Message> {
-        let title = match self {
-            CurveSolver::Horizontal(_) => "Horizontal Curves",
-            CurveSolver::Vertical(_) => "Vertical Curves",
+        let tab = self.tabs.current();
+        let title = match tab.project.view {
+            CurveType::Horizontal => "Horizontal Curves",
+            CurveType::Vertical => "Vertical Curves",
         };
-        let file = match self {
-            CurveSolver::Horizontal(data) => &data.input_directory,
-            CurveSolver::Vertical(data) => &data.input_directory,
+        let file = match tab.project.directory() {
+            "" => tab
+                .project
+                .export_path(&tab.name, &self.settings.export_folder())
+                .map(|x| format!("{} (automatic)", x))
+                .unwrap_or_default(),
+            directory => directory.to_string(),
         };
 
-        let title_header = text(title)
-            .width(Length::Fill)
-            .size(50)
-            .style(Color::from([0.5, 0.5, 0.5]))
-            .horizontal_alignment(alignment::Horizontal::Center);
         let task_row = row![
             button("Switch Curve Type").on_press(Message::SwitchCurveType),
+            button("Undo").on_press_maybe(tab.history.can_undo().then_some(Message::Undo)),
+            button("Redo").on_press_maybe(tab.history.can_redo().then_some(Message::Redo)),
+            button("Shortcuts").on_press(Message::ShortcutsToggle),
+            button("Settings").on_press(Message::SettingsToggle),
             // Space::with_width(Length::Fill),
             button(exit_icon()).on_press(Message::FileDialog),
             text(file).width(Length::Fill),
+            pick_list(self.settings.recent.clone(), None, Message::RecentSelect)
+                .placeholder("Recent projects"),
             self.display_export(),
         ]
         .width(Length::Fill)
         .spacing(H_S);
-        column![title_header, task_row]
+        let status_row = match &self.status {
+            Ok(status) if status.is_empty() => row![],
+            Ok(status) => row![good_check_icon(), text(format!(" {}", status))],
+            Err(error) => error_row(error),
+        };
+        column![title_header(title), task_row, status_row]
             .spacing(H_S)
             .width(Length::Fill)
     }
 
+    /// Export buttons, disabled while a mandatory input is missing or doesn't parse.
     fn display_export(&self) -> Row<'_, Message> {
         let mut export_row = row![].spacing(H_S);
         let labels = [".txt", ".pdf"];
         let msg = [Message::ExportText, Message::ExportPDF];
-        let binding = match self {
-            Self::Horizontal(data) => data.success_flags,
-            Self::Vertical(data) => data.success_flags,
+        let project = &self.tabs.current().project;
+        let (binding, valid) = match project.view {
+            CurveType::Horizontal => {
+                let data = &project.horizontal;
+                (data.success_flags, data.mandatory_valid())
+            }
+            CurveType::Vertical => {
+                let data = &project.vertical;
+                (data.success_flags, data.mandatory_valid())
+            }
         };
 
         for (i, k) in binding.iter().enumerate() {
-            match k {
+            let on_press = valid.then(|| msg[i].clone());
+            export_row = match k {
                 ExportSuccess::Failure => {
-                    export_row =
-                        export_row.push(button(exclam_icon()).on_press(msg[i].clone()).width(42))
+                    export_row.push(button(exclam_icon()).on_press_maybe(on_press).width(42))
                 }
                 ExportSuccess::None => {
-                    export_row =
-                        export_row.push(button(labels[i]).on_press(msg[i].clone()).width(42))
+                    export_row.push(button(labels[i]).on_press_maybe(on_press).width(42))
                 }
                 ExportSuccess::Success => {
-                    export_row = export_row
-                        .push(button(good_check_icon()).on_press(msg[i].clone()).width(42))
+                    export_row.push(button(good_check_icon()).on_press_maybe(on_press).width(42))
                 }
             };
         }
         export_row
     }
+}
 
-    pub fn next_page(&mut self) {
-        match self {
-            CurveSolver::Vertical(vertical_data) => {
-                *self = CurveSolver::Horizontal(HorizontalData::default())
-            }
-            CurveSolver::Horizontal(horizontal_data) => {
-                *self = CurveSolver::Vertical(VerticalData::default())
-            }
+impl Project {
+    pub fn new(design_standard: DesignStandard) -> Self {
+        let mut project = Project::default();
+        project.vertical.input_design_standard = design_standard;
+        project.horizontal.input_design_standard = design_standard;
+        project
+    }
+
+    /// Export path without the extension, the file chosen or an automatic name in the folder.
+    pub fn export_path(&self, name: &str, folder: &str) -> Result<String> {
+        match self.directory() {
+            "" => Ok(Path::new(folder)
+                .join(self.file_name(name)?)
+                .to_string_lossy()
+                .to_string()),
+            directory => Ok(directory.to_string()),
         }
     }
 
+    /// Automatic export name from the curve name and the PVI or PI station.
+    pub fn file_name(&self, name: &str) -> Result<String> {
+        let (label, station) = match self.view {
+            CurveType::Vertical => ("PVI", self.vertical.to_vertical_curve()?.stations.pvi),
+            CurveType::Horizontal => ("PI", self.horizontal.to_horizontal_curve()?.stations.pi),
+        };
+        Ok(export_file_name(name, label, &station))
+    }
+
+    /// Writes the text or pdf report, returning the path written without the extension.
+    pub fn export(&mut self, name: &str, folder: &str, pdf: bool) -> Result<String> {
+        let valid = match self.view {
+            CurveType::Vertical => self.vertical.mandatory_valid(),
+            CurveType::Horizontal => self.horizontal.mandatory_valid(),
+        };
+        let result = match valid {
+            false => Err(anyhow!("A mandatory input is missing or invalid.")),
+            true => self.export_path(name, folder).and_then(|path| {
+                match (self.view, pdf) {
+                    (CurveType::Vertical, false) => self.vertical.export_txt(&path)?,
+                    (CurveType::Vertical, true) => self.vertical.export_pdf(&path)?,
+                    (CurveType::Horizontal, false) => self.horizontal.export_txt(&path)?,
+                    (CurveType::Horizontal, true) => self.horizontal.export_pdf(&path)?,
+                }
+                // the inputs go next to the report, so recent projects can be opened again
+                self.save(&path)?;
+                Ok(path)
+            }),
+        };
+
+        let flags = match self.view {
+            CurveType::Vertical => &mut self.vertical.success_flags,
+            CurveType::Horizontal => &mut self.horizontal.success_flags,
+        };
+        *flags = [ExportSuccess::None; 2];
+        flags[pdf as usize] = match result.is_ok() {
+            true => ExportSuccess::Success,
+            false => ExportSuccess::Failure,
+        };
+        result
+    }
+
+    fn directory(&self) -> &str {
+        match self.view {
+            CurveType::Vertical => &self.vertical.input_directory,
+            CurveType::Horizontal => &self.horizontal.input_directory,
+        }
+    }
+
+    /// The export folder is shared by both views.
+    fn set_directory(&mut self, directory: String) {
+        self.vertical.input_directory = directory.clone();
+        self.horizontal.input_directory = directory;
+    }
+
+    /// Switches between the vertical and horizontal views, keeping the inputs of both.
+    pub fn next_page(&mut self) {
+        self.set_directory(self.directory().to_string());
+        self.view = self.view.next();
+    }
+
+    /// Inputs without the view, export folder, flags, messages and row selection, which aren't undo steps.
+    fn design(&self) -> Project {
+        let mut project = self.clone();
+        project.view = CurveType::default();
+        project.set_directory(String::new());
+        let vertical = &mut project.vertical;
+        vertical.success_flags = [ExportSuccess::None; 2];
+        vertical.standard_error.clear();
+        vertical.list_error.clear();
+        vertical.selected_obstacle = None;
+        let horizontal = &mut project.horizontal;
+        horizontal.success_flags = [ExportSuccess::None; 2];
+        horizontal.standard_error.clear();
+        horizontal.list_error.clear();
+        horizontal.selected_pin = None;
+        project
+    }
+
+    /// Adds the obstacle or pin typed in, or replaces the selected row with it.
     pub fn add_to_list(&mut self) -> Result<()> {
-        //this is a hack
-        match self {
-            CurveSolver::Vertical(vertical_data) => {
-                let value = coerce_station_value(&vertical_data.input_obstacle_station)?;
+        match self.view {
+            CurveType::Vertical => {
+                let vertical_data = &mut self.vertical;
+                let equations = vertical_data.to_equations()?;
+                let value = equations.parse(&vertical_data.input_obstacle_station)?;
                 let elevation = coerce_elevation(&vertical_data.input_obstacle_elevation)?;
-                let station = Station {
+                let station = equations.tag(Station {
                     value,
                     elevation: Some(elevation),
                     ..Default::default()
-                };
-                vertical_data
-                    .obstacles
-                    .interval
-                    .push((station, vertical_data.input_obstacle_type));
+                });
+                let row = (station, vertical_data.input_obstacle_type);
+                let rows = &mut vertical_data.obstacles.interval;
+                match vertical_data.selected_obstacle.take() {
+                    Some(i) if i < rows.len() => rows[i] = row,
+                    _ => rows.push(row),
+                }
             }
-            CurveSolver::Horizontal(horizontal_data) => {
-                let value = coerce_station_value(&horizontal_data.input_pin_station)?;
-
-                horizontal_data.pin.interval.push(Station {
+            CurveType::Horizontal => {
+                let horizontal_data = &mut self.horizontal;
+                let equations = horizontal_data.to_equations()?;
+                let value = equations.parse(&horizontal_data.input_pin_station)?;
+                let row = equations.tag(Station {
                     value,
                     ..Default::default()
                 });
+                let rows = &mut horizontal_data.pin.interval;
+                match horizontal_data.selected_pin.take() {
+                    Some(i) if i < rows.len() => rows[i] = row,
+                    _ => rows.push(row),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the selected row, or the last row when none is selected.
+    pub fn remove_from_list(&mut self) {
+        match self.view {
+            CurveType::Vertical => {
+                let vertical_data = &mut self.vertical;
+                let rows = &mut vertical_data.obstacles.interval;
+                match vertical_data.selected_obstacle.take() {
+                    Some(i) if i < rows.len() => {
+                        rows.remove(i);
+                    }
+                    _ => {
+                        rows.pop();
+                    }
+                }
+            }
+            CurveType::Horizontal => {
+                let horizontal_data = &mut self.horizontal;
+                let rows = &mut horizontal_data.pin.interval;
+                match horizontal_data.selected_pin.take() {
+                    Some(i) if i < rows.len() => {
+                        rows.remove(i);
+                    }
+                    _ => {
+                        rows.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Edits the obstacle or pin rows. A selected row is loaded into the inputs for editing.
+    pub fn edit_list(&mut self, edit: RowEdit) {
+        match self.view {
+            CurveType::Vertical => {
+                let vertical_data = &mut self.vertical;
+                let rows = &mut vertical_data.obstacles.interval;
+                edit_rows(rows, &mut vertical_data.selected_obstacle, edit);
+                if let (RowEdit::Select(_), Some(i)) = (edit, vertical_data.selected_obstacle) {
+                    let (station, obstacle_type) = rows[i];
+                    vertical_data.input_obstacle_station = station.to_input();
+                    vertical_data.input_obstacle_elevation =
+                        station.elevation.map(|x| x.to_string()).unwrap_or_default();
+                    vertical_data.input_obstacle_type = obstacle_type;
+                }
+            }
+            CurveType::Horizontal => {
+                let horizontal_data = &mut self.horizontal;
+                let rows = &mut horizontal_data.pin.interval;
+                edit_rows(rows, &mut horizontal_data.selected_pin, edit);
+                if let (RowEdit::Select(_), Some(i)) = (edit, horizontal_data.selected_pin) {
+                    horizontal_data.input_pin_station = rows[i].to_input();
+                }
+            }
+        }
+    }
+
+    /// Appends the rows of a comma separated file, see `coerce_obstacle_list` and `coerce_station_list`.
+    pub fn import_list(&mut self, path: &str) -> Result<()> {
+        let contents = std::fs::read_to_string(path)?;
+        match self.view {
+            CurveType::Vertical => {
+                let vertical_data = &mut self.vertical;
+                let equations = vertical_data.to_equations()?;
+                let rows = coerce_obstacle_list(&contents, &equations)?;
+                vertical_data.obstacles.interval.extend(rows);
+            }
+            CurveType::Horizontal => {
+                let horizontal_data = &mut self.horizontal;
+                let equations = horizontal_data.to_equations()?;
+                let rows = coerce_station_list(&contents, &equations)?;
+                horizontal_data.pin.interval.extend(rows);
             }
         }
         Ok(())
     }
+
+    fn set_list_error(&mut self, result: Result<()>) {
+        let error = match result {
+            Ok(_) => String::new(),
+            Err(e) => e.to_string(),
+        };
+        match self.view {
+            CurveType::Vertical => self.vertical.list_error = error,
+            CurveType::Horizontal => self.horizontal.list_error = error,
+        }
+    }
 }
 
 impl From<VerticalDefinition> for HorizontalStationDefinition {
@@ -434,6 +1035,50 @@ impl From<HorizontalStationDefinition> for VerticalDefinition {
 }
 
 // Generic Text
+fn title_header(title: &str) -> Text<'static> {
+    text(title)
+        .width(Length::Fill)
+        .size(TITLE_SIZE)
+        .style(Color::from([0.5, 0.5, 0.5]))
+        .horizontal_alignment(alignment::Horizontal::Center)
+}
+
+fn error_row(error: &str) -> Row<'_, Message> {
+    match error.is_empty() {
+        true => row![],
+        false => row![exclam_icon(), text(format!(" {}", error))],
+    }
+}
+
+fn format_row() -> Row<'static, Message> {
+    let settings = format_settings();
+    row![
+        text("Format:"),
+        button(text(format!("STA .{}", settings.station_decimals)))
+            .on_press(Message::StationDecimalsToggle),
+        button(text(format!("ELEV .{}", settings.elevation_decimals)))
+            .on_press(Message::ElevationDecimalsToggle),
+        button(text(format!("LEN .{}", settings.length_decimals)))
+            .on_press(Message::LengthDecimalsToggle),
+        button(text(format!("{}", settings.station_base.value())))
+            .on_press(Message::StationBaseToggle),
+        button(text(format!("{:?}", settings.angle_format))).on_press(Message::AngleFormatToggle),
+        button(text(format!("{:?}", settings.label_style))).on_press(Message::LabelStyleToggle),
+    ]
+    .spacing(H_S)
+}
+
+fn maneuver_row(sight_type: SightType) -> Row<'static, Message> {
+    match sight_type {
+        SightType::Decision(maneuver) => row![
+            text(format!("Avoidance Maneuver: {:?}", maneuver)),
+            button(stext('M')).on_press(Message::ManeuverToggle),
+        ]
+        .spacing(H_S),
+        _ => row![],
+    }
+}
+
 fn stext(character: char) -> Text<'static> {
     text(character.to_string())
         .horizontal_alignment(alignment::Horizontal::Center)
@@ -489,8 +1134,139 @@ fn notification_icon() -> Text<'static> {
 fn exit_icon() -> Text<'static> {
     icon2('\u{2e}')
 }
+const TITLE_SIZE: u16 = 50;
 const SUBTITLE_SIZE: u16 = 22;
 
 fn subtitle(str: &str) -> Text<'static> {
     text(str).size(SUBTITLE_SIZE)
 }
+
+#[cfg(test)]
+mod frontend_tests {
+    use super::{CurveSolver, CurveType, Message, Project, Tabs, UserSettings};
+    use crate::vertical::VerticalDefinition;
+    use iced::Application;
+
+    #[test]
+    fn switching_keeps_inputs() {
+        let mut project = Project::default();
+        project.vertical.input_station = "12+34".to_string();
+        project.set_directory("/tmp/curve".to_string());
+
+        project.next_page();
+        assert_eq!(project.view, CurveType::Horizontal);
+        project.horizontal.input_radius = "1000".to_string();
+        assert_eq!(project.directory(), "/tmp/curve");
+
+        project.next_page();
+        assert_eq!(project.view, CurveType::Vertical);
+        assert_eq!(project.vertical.input_station, "12+34");
+        assert_eq!(project.horizontal.input_radius, "1000");
+    }
+
+    #[test]
+    fn design_changes() {
+        let mut project = Project::default();
+        let before = project.design();
+
+        // a failed add only leaves a message behind
+        let result = project.add_to_list();
+        assert!(result.is_err());
+        project.set_list_error(result);
+        project.next_page();
+        project.set_directory("/tmp/curve".to_string());
+        assert!(project.design() == before);
+
+        project.horizontal.input_radius = "1000".to_string();
+        assert!(project.design() != before);
+    }
+
+    #[test]
+    fn recent_opens_project() {
+        let mut solver = CurveSolver {
+            tabs: Tabs::default(),
+            comparing: false,
+            shortcuts: false,
+            settings: UserSettings::default(),
+            settings_open: false,
+            settings_error: String::new(),
+            settings_writable: false,
+            status: Ok(String::new()),
+        };
+        let folder = std::env::temp_dir().join("floracad_recent_opens_project");
+        std::fs::create_dir_all(&folder).unwrap();
+        let path = folder
+            .join("Bridge_PVI_12+34")
+            .to_string_lossy()
+            .to_string();
+        let mut project = Project::default();
+        project.vertical.input_length = "500".to_string();
+        project.save(&path).unwrap();
+
+        let _ = solver.update(Message::RecentSelect(path.clone()));
+        let tab = solver.tabs.current();
+        assert_eq!(tab.name, "Bridge_PVI_12+34");
+        assert_eq!(tab.project.vertical.input_length, "500");
+        assert_eq!(tab.project.directory(), path);
+        assert!(!tab.history.can_undo());
+
+        // the curve is already open, so it's selected rather than opened twice
+        let _ = solver.update(Message::TabSelect(0));
+        let _ = solver.update(Message::RecentSelect(path.clone()));
+        assert_eq!(solver.tabs.current().project.directory(), path);
+        let _ = solver.update(Message::TabClose);
+        assert!(!solver.tabs.select_directory(&path));
+
+        let _ = solver.update(Message::RecentSelect(format!("{}_gone", path)));
+        assert!(solver.status.is_err());
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn equations_retag_pins() {
+        let mut solver = CurveSolver {
+            tabs: Tabs::default(),
+            comparing: false,
+            shortcuts: false,
+            settings: UserSettings::default(),
+            settings_open: false,
+            settings_error: String::new(),
+            settings_writable: false,
+            status: Ok(String::new()),
+        };
+        let project = &mut solver.tabs.current_mut().project;
+        project.next_page();
+        project.horizontal.input_station_equations = "125+40.22 BK = 125+00 AH".to_string();
+        project.horizontal.input_pin_station = "125+20 AH".to_string();
+        project.add_to_list().unwrap();
+
+        let _ = solver.update(Message::StationEquationsModify(String::new()));
+        let pin = solver.tabs.current().project.horizontal.pin.interval[0];
+        assert_eq!(pin.to_input(), "125+60.22");
+        assert!(pin.equation.is_none());
+    }
+
+    #[test]
+    fn export_paths() {
+        let mut project = Project::default();
+        assert!(project.export_path("Curve 1", "/tmp").is_err());
+
+        let vertical = &mut project.vertical;
+        vertical.input_method = VerticalDefinition::PVI;
+        vertical.input_station = "10284+50".to_string();
+        vertical.input_elevation = "1001.38".to_string();
+        vertical.input_incoming_grade = "0.44%".to_string();
+        vertical.input_outgoing_grade = "-0.57%".to_string();
+        vertical.input_length = "500".to_string();
+        assert_eq!(
+            project.export_path("L=500 / alt", "/tmp").unwrap(),
+            "/tmp/L_500___alt_PVI_10284+50"
+        );
+
+        project.set_directory("/home/zi/report".to_string());
+        assert_eq!(
+            project.export_path("L=500", "/tmp").unwrap(),
+            "/home/zi/report"
+        );
+    }
+}
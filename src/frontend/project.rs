@@ -0,0 +1,381 @@
+use std::fmt::Debug;
+use std::fs;
+
+use thiserror::Error;
+
+use crate::frontend::*;
+use crate::horizontal::{
+    stakeout::{SetupDefinition, StakeoutMethod},
+    through::PointDefinition,
+    widening::DesignVehicle,
+};
+
+/// Project file saved next to an export, the export path with this extension.
+pub fn project_path(path: &str) -> String {
+    format!("{}.curve", path)
+}
+
+/// Options reached by cycling `next` from the default.
+fn cycle<T: Copy + Default + PartialEq>(next: fn(T) -> T) -> Vec<T> {
+    let mut options = vec![T::default()];
+    let mut option = next(T::default());
+    while option != T::default() && !options.contains(&option) {
+        options.push(option);
+        option = next(option);
+    }
+    options
+}
+
+/// Option whose debug name is the value.
+fn cycled<T: Debug + Copy + Default + PartialEq>(
+    next: fn(T) -> T,
+    value: &str,
+    line: usize,
+) -> Result<T, Error> {
+    cycle(next)
+        .into_iter()
+        .find(|x| format!("{:?}", x).eq_ignore_ascii_case(value))
+        .ok_or(Error::ParseValue { line })
+}
+
+/// Sight types with every decision maneuver.
+fn next_sight(sight: SightType) -> SightType {
+    match sight {
+        SightType::Decision(x) if x.next() != DecisionManeuver::default() => {
+            SightType::Decision(x.next())
+        }
+        _ => sight.next(),
+    }
+}
+
+fn number(value: &str, line: usize) -> Result<f64, Error> {
+    match value.parse::<f64>() {
+        Ok(x) if x.is_finite() => Ok(x),
+        _ => Err(Error::ParseValue { line }),
+    }
+}
+
+fn vertical_texts(data: &mut VerticalData) -> [(&'static str, &mut String); 13] {
+    [
+        ("station", &mut data.input_station),
+        ("elevation", &mut data.input_elevation),
+        ("incoming_grade", &mut data.input_incoming_grade),
+        ("outgoing_grade", &mut data.input_outgoing_grade),
+        ("length", &mut data.input_length),
+        ("k_value", &mut data.input_k_value),
+        ("station_interval", &mut data.input_station_interval),
+        ("station_equations", &mut data.input_station_equations),
+        ("design_speed", &mut data.input_design_speed),
+        ("reaction_time", &mut data.input_reaction_time),
+        ("deceleration", &mut data.input_deceleration),
+        ("obstacle_station", &mut data.input_obstacle_station),
+        ("obstacle_elevation", &mut data.input_obstacle_elevation),
+    ]
+}
+
+fn horizontal_texts(data: &mut HorizontalData) -> [(&'static str, &mut String); 24] {
+    [
+        ("station", &mut data.input_station),
+        ("length", &mut data.input_length),
+        ("radius", &mut data.input_radius),
+        ("curve_angle", &mut data.input_curve_angle),
+        ("tangent", &mut data.input_tangent),
+        ("degree", &mut data.input_degree),
+        ("long_chord", &mut data.input_long_chord),
+        ("middle_ordinate", &mut data.input_middle_ordinate),
+        ("external", &mut data.input_external),
+        ("pi_point", &mut data.input_pi_point),
+        ("back_bearing", &mut data.input_back_bearing),
+        ("ahead_bearing", &mut data.input_ahead_bearing),
+        ("point", &mut data.input_point),
+        ("station_interval", &mut data.input_station_interval),
+        ("station_equations", &mut data.input_station_equations),
+        ("occupied", &mut data.input_occupied),
+        ("backsight", &mut data.input_backsight),
+        ("locate_points", &mut data.input_locate_points),
+        ("design_speed", &mut data.input_design_speed),
+        ("m", &mut data.input_m),
+        ("offsets", &mut data.input_offsets),
+        ("lanes", &mut data.input_lanes),
+        ("traveled_way", &mut data.input_traveled_way),
+        ("pin_station", &mut data.input_pin_station),
+    ]
+}
+
+impl Project {
+    /// Inputs of both views as `key = value` lines, see `from_file`.
+    pub fn to_file(&self) -> String {
+        // the text inputs are listed once, for saving and loading
+        let mut project = self.clone();
+        let v = &self.vertical;
+        let h = &self.horizontal;
+        let mut lines = vec![format!("view = {:?}", self.view)];
+
+        lines.extend(
+            vertical_texts(&mut project.vertical)
+                .iter()
+                .map(|(key, value)| format!("vertical.{} = {}", key, value)),
+        );
+        lines.extend([
+            format!("vertical.method = {:?}", v.input_method),
+            format!("vertical.length_method = {:?}", v.input_length_method),
+            format!("vertical.sight_type = {:?}", v.input_sight_type),
+            format!("vertical.speed_lookup = {:?}", v.input_speed_lookup),
+            format!("vertical.design_standard = {:?}", v.input_design_standard),
+            format!("vertical.sustained_downgrade = {}", v.sustained_downgrade),
+            format!("vertical.obstacle_type = {:?}", v.input_obstacle_type),
+        ]);
+        lines.extend(v.obstacles.interval.iter().map(|(station, obstacle_type)| {
+            format!(
+                "vertical.obstacle = {}, {}, {:?}",
+                station.value,
+                station.elevation.unwrap_or_default(),
+                obstacle_type
+            )
+        }));
+
+        lines.extend(
+            horizontal_texts(&mut project.horizontal)
+                .iter()
+                .map(|(key, value)| format!("horizontal.{} = {}", key, value)),
+        );
+        lines.extend([
+            format!("horizontal.station_method = {:?}", h.input_station_method),
+            format!("horizontal.build_method = {:?}", h.input_build_method),
+            format!(
+                "horizontal.degree_definition = {:?}",
+                h.input_degree_definition
+            ),
+            format!("horizontal.direction = {:?}", h.input_direction),
+            format!("horizontal.point_method = {:?}", h.input_point_method),
+            format!("horizontal.stakeout_method = {:?}", h.input_stakeout_method),
+            format!("horizontal.setup_method = {:?}", h.input_setup_method),
+            format!("horizontal.sight_type = {:?}", h.input_sight_type),
+            format!("horizontal.speed_lookup = {:?}", h.input_speed_lookup),
+            format!("horizontal.design_standard = {:?}", h.input_design_standard),
+            format!("horizontal.sustained_downgrade = {}", h.sustained_downgrade),
+            format!("horizontal.design_vehicle = {:?}", h.input_design_vehicle),
+        ]);
+        lines.extend(
+            h.pin
+                .interval
+                .iter()
+                .map(|x| format!("horizontal.pin = {}", x.value)),
+        );
+        lines.join("\n")
+    }
+
+    /// Parses a project from `key = value` lines. Missing keys keep their defaults, and bad lines are skipped and returned.
+    pub fn from_file(raw_data: &str) -> (Self, Vec<Error>) {
+        let mut project = Project::default();
+        let mut errors = Vec::new();
+
+        for (i, line) in raw_data.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let result = match line.split_once('=') {
+                Some((key, value)) => project.set(key.trim(), value.trim(), i + 1),
+                None => Err(Error::ParseLine { line: i + 1 }),
+            };
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        }
+
+        // rows are kept in true stations, tagged once the equations are known
+        let equations = project.vertical.to_equations().unwrap_or_default();
+        for (station, _) in project.vertical.obstacles.interval.iter_mut() {
+            *station = equations.tag(*station);
+        }
+        let equations = project.horizontal.to_equations().unwrap_or_default();
+        for station in project.horizontal.pin.interval.iter_mut() {
+            *station = equations.tag(*station);
+        }
+
+        (project, errors)
+    }
+
+    fn set(&mut self, key: &str, value: &str, line: usize) -> Result<(), Error> {
+        if key == "view" {
+            self.view = cycled(CurveType::next, value, line)?;
+            return Ok(());
+        }
+        if let Some(key) = key.strip_prefix("vertical.") {
+            if let Some((_, text)) = vertical_texts(&mut self.vertical)
+                .into_iter()
+                .find(|(x, _)| *x == key)
+            {
+                *text = value.to_string();
+                return Ok(());
+            }
+            let v = &mut self.vertical;
+            match key {
+                "method" => v.input_method = cycled(VerticalDefinition::next, value, line)?,
+                "length_method" => {
+                    v.input_length_method = cycled(VerticalLengthDefinition::next, value, line)?
+                }
+                "sight_type" => v.input_sight_type = cycled(next_sight, value, line)?,
+                "speed_lookup" => v.input_speed_lookup = cycled(SpeedLookup::next, value, line)?,
+                "design_standard" => {
+                    v.input_design_standard = cycled(DesignStandard::next, value, line)?
+                }
+                "sustained_downgrade" => {
+                    v.sustained_downgrade = value.parse().map_err(|_| Error::ParseValue { line })?
+                }
+                "obstacle_type" => v.input_obstacle_type = cycled(ObstacleType::next, value, line)?,
+                "obstacle" => {
+                    let cells = value.split(',').map(str::trim).collect::<Vec<&str>>();
+                    let [station, elevation, obstacle_type] = cells[..] else {
+                        return Err(Error::ParseValue { line });
+                    };
+                    let station = Station {
+                        value: number(station, line)?,
+                        elevation: Some(number(elevation, line)?),
+                        ..Default::default()
+                    };
+                    let obstacle_type = cycled(ObstacleType::next, obstacle_type, line)?;
+                    v.obstacles.interval.push((station, obstacle_type));
+                }
+                _ => return Err(Error::UnknownKey { line }),
+            }
+            return Ok(());
+        }
+        if let Some(key) = key.strip_prefix("horizontal.") {
+            if let Some((_, text)) = horizontal_texts(&mut self.horizontal)
+                .into_iter()
+                .find(|(x, _)| *x == key)
+            {
+                *text = value.to_string();
+                return Ok(());
+            }
+            let h = &mut self.horizontal;
+            match key {
+                "station_method" => {
+                    h.input_station_method = cycled(HorizontalStationDefinition::next, value, line)?
+                }
+                "build_method" => {
+                    h.input_build_method = cycled(HorizontalBuildDefinition::next, value, line)?
+                }
+                "degree_definition" => {
+                    h.input_degree_definition = cycled(DegreeDefinition::next, value, line)?
+                }
+                "direction" => h.input_direction = cycled(CurveDirection::next, value, line)?,
+                "point_method" => {
+                    h.input_point_method = cycled(PointDefinition::next, value, line)?
+                }
+                "stakeout_method" => {
+                    h.input_stakeout_method = cycled(StakeoutMethod::next, value, line)?
+                }
+                "setup_method" => {
+                    h.input_setup_method = cycled(SetupDefinition::next, value, line)?
+                }
+                "sight_type" => h.input_sight_type = cycled(next_sight, value, line)?,
+                "speed_lookup" => h.input_speed_lookup = cycled(SpeedLookup::next, value, line)?,
+                "design_standard" => {
+                    h.input_design_standard = cycled(DesignStandard::next, value, line)?
+                }
+                "sustained_downgrade" => {
+                    h.sustained_downgrade = value.parse().map_err(|_| Error::ParseValue { line })?
+                }
+                "design_vehicle" => {
+                    h.input_design_vehicle = cycled(DesignVehicle::next, value, line)?
+                }
+                "pin" => h.pin.interval.push(Station {
+                    value: number(value, line)?,
+                    ..Default::default()
+                }),
+                _ => return Err(Error::UnknownKey { line }),
+            }
+            return Ok(());
+        }
+        Err(Error::UnknownKey { line })
+    }
+
+    /// Saves the inputs next to the export at `path`.
+    pub fn save(&self, path: &str) -> Result<()> {
+        fs::write(project_path(path), self.to_file())?;
+        Ok(())
+    }
+
+    /// Project saved next to the export at `path`, exporting to that path again, with the lines that were skipped.
+    pub fn open(path: &str) -> Result<(Self, Vec<Error>)> {
+        let raw_data = fs::read_to_string(project_path(path))
+            .map_err(|_| anyhow!("No saved project next to {}.", path))?;
+        let (mut project, errors) = Project::from_file(&raw_data);
+        project.set_directory(path.to_string());
+        Ok((project, errors))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    /// Line is missing an "=" sign.
+    #[error("Project line {line} is missing an \"=\" sign and was skipped.")]
+    ParseLine { line: usize },
+    /// Line has a misconfigured value.
+    #[error("Project line {line} has an unexpected value and was skipped.")]
+    ParseValue { line: usize },
+    /// Line has an unknown key.
+    #[error("Project line {line} has an unknown key and was skipped.")]
+    UnknownKey { line: usize },
+}
+
+#[cfg(test)]
+mod project_tests {
+    use super::{Error, Project};
+    use crate::datatypes::{DecisionManeuver, DesignStandard, SightType, Station};
+    use crate::frontend::CurveType;
+    use crate::horizontal::HorizontalBuildDefinition;
+    use crate::vertical::ObstacleType;
+
+    #[test]
+    fn file_round_trip() {
+        let mut project = Project::new(DesignStandard::AASHTO);
+        project.view = CurveType::Horizontal;
+        let vertical = &mut project.vertical;
+        vertical.input_station = "125+20 AH".to_string();
+        vertical.input_station_equations = "125+40.22 BK = 125+00 AH".to_string();
+        vertical.input_sight_type = SightType::Decision(DecisionManeuver::C);
+        vertical.sustained_downgrade = true;
+        let station = vertical.to_equations().unwrap().tag(Station {
+            value: 12560.22,
+            elevation: Some(98.125),
+            ..Default::default()
+        });
+        vertical
+            .obstacles
+            .interval
+            .push((station, ObstacleType::Below));
+        let horizontal = &mut project.horizontal;
+        horizontal.input_radius = "1000".to_string();
+        horizontal.input_curve_angle = "25d10'05\" LT".to_string();
+        horizontal.input_build_method = HorizontalBuildDefinition::RadiusTangent;
+        horizontal.pin.interval.push(Station {
+            value: 1210.5,
+            ..Default::default()
+        });
+
+        let (parsed, errors) = Project::from_file(&project.to_file());
+        assert!(errors.is_empty());
+        assert_eq!(parsed.view, CurveType::Horizontal);
+        assert_eq!(
+            parsed.vertical.obstacles.interval[0].0.to_input(),
+            "125+20 AH"
+        );
+        assert!(parsed.design() == project.design());
+
+        let (partial, errors) =
+            Project::from_file("vertical.radius = 5\nhorizontal.direction = Up\nview Vertical");
+        assert!(matches!(
+            errors[..],
+            [
+                Error::UnknownKey { line: 1 },
+                Error::ParseValue { line: 2 },
+                Error::ParseLine { line: 3 }
+            ]
+        ));
+        assert!(partial == Project::default());
+    }
+}
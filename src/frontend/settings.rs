@@ -0,0 +1,333 @@
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use iced::theme::Palette;
+use iced::widget::{column, text, Column};
+use thiserror::Error;
+
+use crate::frontend::*;
+
+const SCALES: [f64; 5] = [0.75, 1.0, 1.25, 1.5, 2.0];
+const RECENT_LIMIT: usize = 8;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AppTheme {
+    #[default]
+    Light,
+    Dark,
+    HighContrast,
+}
+
+impl AppTheme {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Light => Self::Dark,
+            Self::Dark => Self::HighContrast,
+            Self::HighContrast => Self::Light,
+        }
+    }
+
+    pub fn to_theme(self) -> Theme {
+        match self {
+            Self::Light => Theme::Light,
+            Self::Dark => Theme::Dark,
+            Self::HighContrast => Theme::custom(Palette {
+                background: Color::BLACK,
+                text: Color::WHITE,
+                primary: Color::from_rgb(1.0, 0.85, 0.0),
+                success: Color::from_rgb(0.0, 1.0, 0.4),
+                danger: Color::from_rgb(1.0, 0.3, 0.3),
+            }),
+        }
+    }
+}
+
+/// Preferences kept between sessions in the user's config directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserSettings {
+    pub theme: AppTheme,
+    pub scale: f64,
+    pub design_standard: DesignStandard,
+    pub format: FormatSettings,
+    pub export_folder: String,
+    pub recent: Vec<String>, // exported paths without the extension, latest first
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        UserSettings {
+            theme: AppTheme::default(),
+            scale: 1.0,
+            design_standard: DesignStandard::default(),
+            format: FormatSettings::default(),
+            export_folder: String::new(),
+            recent: Vec::new(),
+        }
+    }
+}
+
+/// Cycles the UI scales, an unlisted scale goes back to 100%.
+pub fn next_scale(scale: f64) -> f64 {
+    match SCALES.iter().position(|x| *x == scale) {
+        Some(i) => SCALES[(i + 1) % SCALES.len()],
+        None => 1.0,
+    }
+}
+
+/// Cycles the built-in standards, loaded standards aren't available at start up.
+pub fn next_default_standard(standard: DesignStandard) -> DesignStandard {
+    match standard {
+        DesignStandard::AASHTO => DesignStandard::CALTRANS,
+        _ => DesignStandard::AASHTO,
+    }
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    Some(
+        dirs_next::config_dir()?
+            .join("floracad")
+            .join("settings.txt"),
+    )
+}
+
+impl UserSettings {
+    /// Settings from the config file, or the defaults when there is none, with the lines that were skipped.
+    pub fn load() -> Result<(Self, Vec<Error>)> {
+        match config_path() {
+            Some(path) if path.exists() => Ok(UserSettings::from(&fs::read_to_string(path)?)),
+            _ => Ok((UserSettings::default(), Vec::new())),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = config_path().ok_or(anyhow!("No config directory found."))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_config())?;
+        Ok(())
+    }
+
+    /// Export folder, the documents folder until one is chosen.
+    pub fn export_folder(&self) -> String {
+        match self.export_folder.is_empty() {
+            false => self.export_folder.clone(),
+            true => dirs_next::document_dir()
+                .or_else(dirs_next::home_dir)
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Moves the path to the top of the recent exports.
+    pub fn add_recent(&mut self, path: String) {
+        self.recent.retain(|x| *x != path);
+        self.recent.insert(0, path);
+        self.recent.truncate(RECENT_LIMIT);
+    }
+
+    /// Parses settings from `key = value` lines, lines starting with `#` are comments. Missing keys keep their defaults, and bad lines are skipped and returned.
+    pub fn from(raw_data: &str) -> (Self, Vec<Error>) {
+        let mut settings = UserSettings::default();
+        let mut errors = Vec::new();
+
+        for (i, line) in raw_data.lines().enumerate() {
+            // paths may hold a '#', so only whole lines are comments
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let result = match line.split_once('=') {
+                Some((key, value)) => settings.set(key.trim(), value.trim(), i + 1),
+                None => Err(Error::ParseLine { line: i + 1 }),
+            };
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        }
+        settings.recent.truncate(RECENT_LIMIT);
+
+        (settings, errors)
+    }
+
+    fn set(&mut self, key: &str, value: &str, line: usize) -> Result<(), Error> {
+        let format = &mut self.format;
+        let decimals = || match value.parse::<usize>() {
+            Ok(x) if x < 5 => Ok(x),
+            _ => Err(Error::ParseValue { line }),
+        };
+
+        match key {
+            "theme" => {
+                self.theme = named(
+                    &[AppTheme::Light, AppTheme::Dark, AppTheme::HighContrast],
+                    value,
+                    line,
+                )?
+            }
+            "scale" => {
+                self.scale = match value.parse::<f64>() {
+                    Ok(x) if SCALES.contains(&x) => x,
+                    _ => return Err(Error::ParseValue { line }),
+                }
+            }
+            "design_standard" => {
+                self.design_standard = named(
+                    &[DesignStandard::AASHTO, DesignStandard::CALTRANS],
+                    value,
+                    line,
+                )?
+            }
+            "station_decimals" => format.station_decimals = decimals()?,
+            "elevation_decimals" => format.elevation_decimals = decimals()?,
+            "length_decimals" => format.length_decimals = decimals()?,
+            "station_base" => {
+                format.station_base =
+                    named(&[StationBase::Hundred, StationBase::Thousand], value, line)?
+            }
+            "angle_format" => {
+                format.angle_format = named(
+                    &[
+                        AngleFormat::All,
+                        AngleFormat::DMS,
+                        AngleFormat::DD,
+                        AngleFormat::RAD,
+                    ],
+                    value,
+                    line,
+                )?
+            }
+            "label_style" => {
+                format.label_style = named(&[LabelStyle::Short, LabelStyle::Long], value, line)?
+            }
+            "units" => format.units = named(&[Units::Feet, Units::Metres], value, line)?,
+            "export_folder" => self.export_folder = value.to_string(),
+            "recent" => self.recent.push(value.to_string()),
+            _ => return Err(Error::UnknownKey { line }),
+        }
+        Ok(())
+    }
+
+    pub fn to_config(&self) -> String {
+        let format = &self.format;
+        let mut lines = vec![
+            format!("theme = {:?}", self.theme),
+            format!("scale = {}", self.scale),
+            format!("design_standard = {:?}", self.design_standard),
+            format!("station_decimals = {}", format.station_decimals),
+            format!("elevation_decimals = {}", format.elevation_decimals),
+            format!("length_decimals = {}", format.length_decimals),
+            format!("station_base = {:?}", format.station_base),
+            format!("angle_format = {:?}", format.angle_format),
+            format!("label_style = {:?}", format.label_style),
+            format!("units = {:?}", format.units),
+            format!("export_folder = {}", self.export_folder),
+        ];
+        lines.extend(self.recent.iter().map(|x| format!("recent = {}", x)));
+        lines.join("\n")
+    }
+
+    pub fn settings_block<'a>(&'a self, error: &'a str) -> Column<'a, Message> {
+        column![
+            subtitle("Settings"),
+            row![
+                text("Theme:"),
+                button(text(format!("{:?}", self.theme))).on_press(Message::ThemeToggle),
+                text("Scale:"),
+                button(text(format!("{:.0}%", self.scale * 100.0))).on_press(Message::ScaleToggle),
+                text("Default Standard:"),
+                button(text(format!("{}", self.design_standard)))
+                    .on_press(Message::DefaultStandardToggle),
+                text("Units:"),
+                button(text(format!("{:?}", self.format.units))).on_press(Message::UnitsToggle),
+            ]
+            .spacing(H_S)
+            .align_items(iced::Alignment::Center),
+            row![
+                text("Export Folder:"),
+                button(exit_icon()).on_press(Message::ExportFolderSelect),
+                text(self.export_folder()),
+            ]
+            .spacing(H_S)
+            .align_items(iced::Alignment::Center),
+            format_row(),
+            error_row(error),
+        ]
+        .spacing(H_S)
+    }
+}
+
+/// Option whose debug name is the value.
+fn named<T: Debug + Copy>(options: &[T], value: &str, line: usize) -> Result<T, Error> {
+    options
+        .iter()
+        .find(|x| format!("{:?}", x).eq_ignore_ascii_case(value))
+        .copied()
+        .ok_or(Error::ParseValue { line })
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    /// Line is missing an "=" sign.
+    #[error("Settings line {line} is missing an \"=\" sign and was skipped.")]
+    ParseLine { line: usize },
+    /// Line has a misconfigured value.
+    #[error("Settings line {line} has an unexpected value and was skipped.")]
+    ParseValue { line: usize },
+    /// Line has an unknown key.
+    #[error("Settings line {line} has an unknown key and was skipped.")]
+    UnknownKey { line: usize },
+}
+
+#[cfg(test)]
+mod settings_tests {
+    use super::{next_scale, AppTheme, Error, UserSettings};
+    use crate::datatypes::DesignStandard;
+    use crate::format::{AngleFormat, StationBase, Units};
+
+    #[test]
+    fn config_round_trip() {
+        let mut settings = UserSettings {
+            theme: AppTheme::HighContrast,
+            scale: next_scale(1.0),
+            design_standard: DesignStandard::AASHTO,
+            ..Default::default()
+        };
+        settings.format.station_base = StationBase::Thousand;
+        settings.format.angle_format = AngleFormat::DD;
+        settings.format.length_decimals = 3;
+        settings.format.units = Units::Metres;
+        settings.export_folder = "/home/zi/curves #2".to_string();
+        for i in 0..10 {
+            settings.add_recent(format!("/home/zi/curves/Curve_{}", i % 9));
+        }
+        assert_eq!(settings.recent.len(), 8);
+        assert_eq!(settings.recent[0], "/home/zi/curves/Curve_0");
+
+        let (parsed, errors) = UserSettings::from(&settings.to_config());
+        assert!(errors.is_empty());
+        assert_eq!(parsed, settings);
+        assert_eq!(parsed.scale, 1.25);
+
+        let (partial, errors) = UserSettings::from("# saved\ntheme = dark\n");
+        assert!(errors.is_empty());
+        assert_eq!(partial.theme, AppTheme::Dark);
+        assert_eq!(partial.scale, 1.0);
+
+        // bad lines are skipped and reported, the rest still loads
+        let (skipped, errors) =
+            UserSettings::from("scale = 7\ncolour = red\ntheme dark\ntheme = dark\nscale = 1.5");
+        assert!(matches!(
+            errors[..],
+            [
+                Error::ParseValue { line: 1 },
+                Error::UnknownKey { line: 2 },
+                Error::ParseLine { line: 3 }
+            ]
+        ));
+        assert_eq!(skipped.theme, AppTheme::Dark);
+        assert_eq!(skipped.scale, 1.5);
+    }
+}
@@ -0,0 +1,145 @@
+use iced::keyboard::{self, KeyCode, Modifiers};
+use iced::widget::{column, text, Column};
+use iced::window::Mode;
+use iced::{event, Event};
+
+use crate::frontend::*;
+
+/// Alt shortcuts, they work when no field is being typed in.
+const ALT_SHORTCUTS: [(KeyCode, Message, &str); 10] = [
+    (KeyCode::C, Message::SwitchCurveType, "Switch curve type"),
+    (KeyCode::M, Message::StationMethodToggle, "Station method"),
+    (KeyCode::D, Message::DesignStandardToggle, "Design standard"),
+    (KeyCode::S, Message::SightTypeToggle, "Sight type"),
+    (KeyCode::O, Message::ObstacleTypeToggle, "Obstacle type"),
+    (KeyCode::A, Message::AddObstacle, "Add obstacle or pin"),
+    (
+        KeyCode::R,
+        Message::RemoveObstacle,
+        "Remove obstacle or pin",
+    ),
+    (KeyCode::T, Message::ExportText, "Export .txt"),
+    (KeyCode::P, Message::ExportPDF, "Export .pdf"),
+    (
+        KeyCode::K,
+        Message::ShortcutsToggle,
+        "Show or hide shortcuts",
+    ),
+];
+
+const OTHER_SHORTCUTS: [(&str, &str); 5] = [
+    ("Tab / Shift+Tab", "Next / previous field"),
+    ("Ctrl+Z", "Undo"),
+    ("Ctrl+Shift+Z", "Redo"),
+    ("Shift+Up", "Fullscreen"),
+    ("Shift+Down", "Windowed"),
+];
+
+/// Maps key presses to messages. Alt shortcuts only apply when no field takes the key, since a focused field types the character too.
+pub fn key_event(event: Event, status: event::Status) -> Option<Message> {
+    match (event, status) {
+        (
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Tab,
+                modifiers,
+            }),
+            event::Status::Ignored,
+        ) => Some(Message::TabPressed {
+            shift: modifiers.shift(),
+        }),
+        (
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers: Modifiers::ALT,
+            }),
+            event::Status::Ignored,
+        ) => alt_shortcut(key_code),
+        // text inputs capture Ctrl+Z without using it
+        (
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Z,
+                modifiers,
+            }),
+            _,
+        ) if modifiers.command() => match modifiers.shift() {
+            true => Some(Message::Redo),
+            false => Some(Message::Undo),
+        },
+        (
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers: Modifiers::SHIFT,
+            }),
+            event::Status::Ignored,
+        ) => match key_code {
+            KeyCode::Up => Some(Message::FullScreenToggle(Mode::Fullscreen)),
+            KeyCode::Down => Some(Message::FullScreenToggle(Mode::Windowed)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn alt_shortcut(key_code: KeyCode) -> Option<Message> {
+    ALT_SHORTCUTS
+        .iter()
+        .find(|(key, _, _)| *key == key_code)
+        .map(|(_, message, _)| message.clone())
+}
+
+/// On-screen list of every shortcut.
+pub fn shortcut_sheet<'a>() -> Column<'a, Message> {
+    let alt = ALT_SHORTCUTS
+        .iter()
+        .map(|(key, _, description)| (format!("Alt+{:?}", key), *description));
+    let other = OTHER_SHORTCUTS
+        .iter()
+        .map(|(key, description)| (key.to_string(), *description));
+
+    let mut sheet = column![subtitle("Keyboard Shortcuts")].spacing(2);
+    for (key, description) in other.chain(alt) {
+        sheet = sheet.push(row![text(key).width(160), text(description)].spacing(H_S));
+    }
+    sheet
+}
+
+#[cfg(test)]
+mod shortcuts_tests {
+    use super::{alt_shortcut, key_event, ALT_SHORTCUTS};
+    use crate::frontend::Message;
+    use iced::keyboard::{self, KeyCode, Modifiers};
+    use iced::{event, Event};
+
+    #[test]
+    fn alt_shortcuts() {
+        assert!(matches!(
+            alt_shortcut(KeyCode::T),
+            Some(Message::ExportText)
+        ));
+        assert!(alt_shortcut(KeyCode::Z).is_none());
+        for (i, (key, _, _)) in ALT_SHORTCUTS.iter().enumerate() {
+            assert!(ALT_SHORTCUTS[i + 1..].iter().all(|(x, _, _)| x != key));
+        }
+    }
+
+    #[test]
+    fn alt_with_field_focused() {
+        let alt_t = || {
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::T,
+                modifiers: Modifiers::ALT,
+            })
+        };
+        assert!(matches!(
+            key_event(alt_t(), event::Status::Ignored),
+            Some(Message::ExportText)
+        ));
+        // a focused field captures the key press and types the character
+        assert!(key_event(alt_t(), event::Status::Captured).is_none());
+        assert!(key_event(
+            Event::Keyboard(keyboard::Event::CharacterReceived('t')),
+            event::Status::Captured
+        )
+        .is_none());
+    }
+}
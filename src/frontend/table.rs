@@ -0,0 +1,98 @@
+use iced::theme;
+use iced::widget::{column, text, Column};
+
+use crate::frontend::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowEdit {
+    Select(usize), // selecting the selected row clears the selection
+    MoveUp(usize),
+    MoveDown(usize),
+    Delete(usize),
+}
+
+/// Applies a row edit, the selection follows the selected row.
+pub fn edit_rows<T>(rows: &mut Vec<T>, selected: &mut Option<usize>, edit: RowEdit) {
+    let len = rows.len();
+    let swap = |rows: &mut Vec<T>, selected: &mut Option<usize>, a: usize, b: usize| {
+        rows.swap(a, b);
+        *selected = match *selected {
+            Some(x) if x == a => Some(b),
+            Some(x) if x == b => Some(a),
+            x => x,
+        };
+    };
+
+    match edit {
+        RowEdit::Select(i) if i < len => {
+            *selected = match *selected == Some(i) {
+                true => None,
+                false => Some(i),
+            }
+        }
+        RowEdit::MoveUp(i) if i > 0 && i < len => swap(rows, selected, i - 1, i),
+        RowEdit::MoveDown(i) if i + 1 < len => swap(rows, selected, i, i + 1),
+        RowEdit::Delete(i) if i < len => {
+            rows.remove(i);
+            *selected = match *selected {
+                Some(x) if x == i => None,
+                Some(x) if x > i => Some(x - 1),
+                x => x,
+            };
+        }
+        _ => (),
+    }
+}
+
+/// Rows that can be selected, moved and deleted.
+pub fn table<'a>(labels: Vec<String>, selected: Option<usize>) -> Column<'a, Message> {
+    let mut table = column![].spacing(2);
+
+    for (i, label) in labels.into_iter().enumerate() {
+        let style = match selected == Some(i) {
+            true => theme::Button::Primary,
+            false => theme::Button::Secondary,
+        };
+        table = table.push(
+            row![
+                button(text(label))
+                    .style(style)
+                    .width(Length::Fill)
+                    .on_press(Message::RowEdit(RowEdit::Select(i))),
+                button(up_arrow_icon()).on_press(Message::RowEdit(RowEdit::MoveUp(i))),
+                button(down_arrow_icon()).on_press(Message::RowEdit(RowEdit::MoveDown(i))),
+                button(stext('x')).on_press(Message::RowEdit(RowEdit::Delete(i))),
+            ]
+            .spacing(H_S),
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod table_tests {
+    use super::{edit_rows, RowEdit};
+
+    #[test]
+    fn row_edits() {
+        let mut rows = vec!['a', 'b', 'c', 'd'];
+        let mut selected = None;
+
+        edit_rows(&mut rows, &mut selected, RowEdit::Select(1));
+        assert_eq!(selected, Some(1));
+        edit_rows(&mut rows, &mut selected, RowEdit::MoveDown(1));
+        assert_eq!(
+            (rows.clone(), selected),
+            (vec!['a', 'c', 'b', 'd'], Some(2))
+        );
+        edit_rows(&mut rows, &mut selected, RowEdit::MoveUp(0));
+        edit_rows(&mut rows, &mut selected, RowEdit::MoveDown(3));
+        assert_eq!(rows, vec!['a', 'c', 'b', 'd']);
+        edit_rows(&mut rows, &mut selected, RowEdit::Delete(0));
+        assert_eq!((rows.clone(), selected), (vec!['c', 'b', 'd'], Some(1)));
+        edit_rows(&mut rows, &mut selected, RowEdit::Delete(1));
+        assert_eq!((rows.clone(), selected), (vec!['c', 'd'], None));
+        edit_rows(&mut rows, &mut selected, RowEdit::Delete(5));
+        assert_eq!(rows.len(), 2);
+    }
+}
@@ -0,0 +1,295 @@
+use std::fmt::Display;
+
+use iced::theme;
+use iced::widget::{checkbox, column, text, Column};
+
+use crate::frontend::*;
+use crate::tables::TableLookup;
+
+/// A named curve with its own undo history.
+#[derive(Debug)]
+pub struct Tab {
+    pub name: String,
+    pub project: Project,
+    pub history: History<Project>,
+    pub compare: bool,
+}
+
+impl Tab {
+    fn new(name: String, project: Project) -> Self {
+        Tab {
+            name,
+            project,
+            history: History::default(),
+            compare: false,
+        }
+    }
+}
+
+/// Open curves, there is always at least one.
+#[derive(Debug)]
+pub struct Tabs {
+    tabs: Vec<Tab>,
+    selected: usize,
+    opened: usize, // numbers the default names
+    design_standard: DesignStandard,
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Tabs::new(DesignStandard::default())
+    }
+}
+
+impl Tabs {
+    /// One empty curve, new curves start with the given standard.
+    pub fn new(design_standard: DesignStandard) -> Self {
+        Tabs {
+            tabs: vec![Tab::new(
+                "Curve 1".to_string(),
+                Project::new(design_standard),
+            )],
+            selected: 0,
+            opened: 1,
+            design_standard,
+        }
+    }
+
+    pub fn set_default_standard(&mut self, design_standard: DesignStandard) {
+        self.design_standard = design_standard;
+    }
+
+    pub fn current(&self) -> &Tab {
+        &self.tabs[self.selected]
+    }
+
+    pub fn current_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.selected]
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.tabs.len() {
+            self.selected = index;
+        }
+    }
+
+    /// Opens an empty curve of the current type next to the current tab.
+    pub fn add(&mut self) {
+        self.opened += 1;
+        let project = Project {
+            view: self.current().project.view,
+            ..Project::new(self.design_standard)
+        };
+        self.insert(Tab::new(format!("Curve {}", self.opened), project));
+    }
+
+    /// Opens a copy of the current curve, without its undo history or export file.
+    pub fn duplicate(&mut self) {
+        let current = self.current();
+        let mut project = current.project.clone();
+        // the copy gets its own automatic name rather than overwriting the original's file
+        project.set_directory(String::new());
+        let tab = Tab::new(format!("{} copy", current.name), project);
+        self.insert(tab);
+    }
+
+    /// Opens a saved curve next to the current tab.
+    pub fn open(&mut self, name: String, project: Project) {
+        self.insert(Tab::new(name, project));
+    }
+
+    /// Selects the tab exporting to the path, if there is one.
+    pub fn select_directory(&mut self, directory: &str) -> bool {
+        match self
+            .tabs
+            .iter()
+            .position(|x| x.project.directory() == directory)
+        {
+            Some(i) => {
+                self.selected = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, tab: Tab) {
+        self.selected += 1;
+        self.tabs.insert(self.selected, tab);
+    }
+
+    /// Closes the current curve, closing the last one leaves an empty curve.
+    pub fn close(&mut self) {
+        self.tabs.remove(self.selected);
+        if self.tabs.is_empty() {
+            *self = Tabs::new(self.design_standard);
+        }
+        self.selected = self.selected.min(self.tabs.len() - 1);
+    }
+
+    pub fn rename(&mut self, name: String) {
+        self.current_mut().name = name;
+    }
+
+    pub fn set_compare(&mut self, index: usize, compare: bool) {
+        if let Some(tab) = self.tabs.get_mut(index) {
+            tab.compare = compare;
+        }
+    }
+
+    pub fn compared(&self) -> impl Iterator<Item = &Tab> {
+        self.tabs.iter().filter(|x| x.compare)
+    }
+
+    /// Tab buttons with the comparison checkboxes, and the tab actions.
+    pub fn tab_row(&self, comparing: bool) -> Column<'_, Message> {
+        let mut tab_row = row![].spacing(H_S);
+        for (i, tab) in self.tabs.iter().enumerate() {
+            let style = match i == self.selected && !comparing {
+                true => theme::Button::Primary,
+                false => theme::Button::Secondary,
+            };
+            tab_row = tab_row.push(
+                row![
+                    checkbox("", tab.compare, move |x| Message::TabCompareCheck(i, x)),
+                    button(text(&tab.name))
+                        .style(style)
+                        .on_press(Message::TabSelect(i)),
+                ]
+                .align_items(iced::Alignment::Center),
+            );
+        }
+
+        let compare_label = match comparing {
+            true => "Back to Curve",
+            false => "Compare",
+        };
+        let action_row = row![
+            text("Name:"),
+            text_input("Curve name", &self.current().name).on_input(Message::TabRename),
+            button("New").on_press(Message::TabNew),
+            button("Duplicate").on_press(Message::TabDuplicate),
+            button("Close").on_press(Message::TabClose),
+            button(compare_label).on_press(Message::CompareToggle),
+        ]
+        .spacing(H_S)
+        .align_items(iced::Alignment::Center);
+
+        column![tab_row, action_row].spacing(H_S)
+    }
+
+    /// Key dimensions and compliance of the ticked curves next to each other.
+    pub fn comparison(&self) -> Column<'_, Message> {
+        let columns = self
+            .compared()
+            .map(|tab| (tab.name.as_str(), tab.project.comparison_rows()))
+            .collect::<Vec<_>>();
+        if columns.is_empty() {
+            return column![row![
+                notification_icon(),
+                text(" Tick the curves to compare.")
+            ]];
+        }
+
+        let mut labels: Vec<&str> = vec![];
+        for (_, rows) in &columns {
+            for (label, _) in rows {
+                if !labels.contains(label) {
+                    labels.push(label);
+                }
+            }
+        }
+
+        let mut header = row![text("").width(COMPARISON_LABEL_WIDTH)].spacing(H_S);
+        for (name, _) in &columns {
+            header = header.push(subtitle(name).width(Length::Fill));
+        }
+        let mut table = column![header].spacing(H_S);
+        for label in labels {
+            let mut line = row![text(label).width(COMPARISON_LABEL_WIDTH)].spacing(H_S);
+            for (_, rows) in &columns {
+                let value = rows
+                    .iter()
+                    .find(|(x, _)| *x == label)
+                    .map(|(_, value)| value.as_str())
+                    .unwrap_or("-");
+                line = line.push(text(value).width(Length::Fill));
+            }
+            table = table.push(line);
+        }
+        table
+    }
+}
+
+const COMPARISON_LABEL_WIDTH: u16 = 160;
+
+impl Project {
+    /// Labelled key dimensions and compliance results of the current view.
+    pub fn comparison_rows(&self) -> Vec<(&'static str, String)> {
+        let mut rows = vec![("Type", format!("{:?}", self.view))];
+        let curve_rows = match self.view {
+            CurveType::Vertical => self.vertical.comparison_rows(),
+            CurveType::Horizontal => self.horizontal.comparison_rows(),
+        };
+        match curve_rows {
+            Ok(curve_rows) => rows.extend(curve_rows),
+            Err(e) => rows.push(("Error", e.to_string())),
+        }
+        rows
+    }
+}
+
+/// A compliance result on one line, e.g. "Pass 520.00 > 480.00".
+pub fn compliance_text<E: Display>(
+    result: Result<(bool, f64, TableLookup), E>,
+    value: f64,
+) -> String {
+    match result {
+        Err(e) => e.to_string(),
+        Ok((true, minimum, _)) => format!("Pass {} > {}", fmt_length(value), fmt_length(minimum)),
+        Ok((false, minimum, _)) => {
+            format!("Fail {} < {}", fmt_length(value), fmt_length(minimum))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tabs_tests {
+    use super::Tabs;
+    use crate::frontend::CurveType;
+
+    #[test]
+    fn tab_actions() {
+        let mut tabs = Tabs::default();
+        tabs.current_mut().project.vertical.input_length = "500".to_string();
+        tabs.current_mut()
+            .project
+            .set_directory("/home/zi/report".to_string());
+        tabs.duplicate();
+        assert_eq!(tabs.current().name, "Curve 1 copy");
+        assert_eq!(tabs.current().project.directory(), "");
+        assert_eq!(tabs.tabs[0].project.directory(), "/home/zi/report");
+        tabs.current_mut().project.vertical.input_length = "700".to_string();
+        tabs.rename("L=700".to_string());
+
+        tabs.select(0);
+        assert_eq!(tabs.current().project.vertical.input_length, "500");
+        tabs.current_mut().project.view = CurveType::Horizontal;
+        tabs.add();
+        assert_eq!(tabs.current().name, "Curve 2");
+        assert_eq!(tabs.current().project.view, CurveType::Horizontal);
+
+        tabs.set_compare(0, true);
+        tabs.set_compare(2, true);
+        let compared = tabs.compared().map(|x| x.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(compared, vec!["Curve 1", "L=700"]);
+
+        tabs.select(2);
+        tabs.close();
+        assert_eq!(tabs.current().name, "Curve 2");
+        tabs.close();
+        tabs.close();
+        assert_eq!(tabs.current().name, "Curve 1");
+        assert_eq!(tabs.current().project.vertical.input_length, "");
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::Display;
+
+use iced::theme;
+use iced::widget::text_input::{Appearance, StyleSheet};
+use iced::widget::{column, text, Column};
+
+use crate::frontend::*;
+
+const ERROR_COLOR: [f32; 3] = [0.85, 0.25, 0.25];
+
+/// Text input outline for values that don't parse.
+struct InvalidInput;
+
+impl InvalidInput {
+    fn outline(appearance: Appearance) -> Appearance {
+        Appearance {
+            border_width: 2.0,
+            border_color: Color::from(ERROR_COLOR),
+            ..appearance
+        }
+    }
+}
+
+impl StyleSheet for InvalidInput {
+    type Style = Theme;
+
+    fn active(&self, style: &Self::Style) -> Appearance {
+        Self::outline(style.active(&theme::TextInput::Default))
+    }
+
+    fn focused(&self, style: &Self::Style) -> Appearance {
+        Self::outline(style.focused(&theme::TextInput::Default))
+    }
+
+    fn hovered(&self, style: &Self::Style) -> Appearance {
+        Self::outline(style.hovered(&theme::TextInput::Default))
+    }
+
+    fn placeholder_color(&self, style: &Self::Style) -> Color {
+        style.placeholder_color(&theme::TextInput::Default)
+    }
+
+    fn value_color(&self, _style: &Self::Style) -> Color {
+        Color::from(ERROR_COLOR)
+    }
+
+    fn disabled_color(&self, style: &Self::Style) -> Color {
+        style.disabled_color(&theme::TextInput::Default)
+    }
+
+    fn selection_color(&self, style: &Self::Style) -> Color {
+        style.selection_color(&theme::TextInput::Default)
+    }
+
+    fn disabled(&self, style: &Self::Style) -> Appearance {
+        style.disabled(&theme::TextInput::Default)
+    }
+}
+
+/// Parse error of a field, `None` when the field is empty or parses.
+pub fn field_error<T, E: Display>(
+    value: &str,
+    parse: impl FnOnce(&str) -> Result<T, E>,
+) -> Option<String> {
+    if value.trim().is_empty() {
+        return None;
+    }
+    parse(value).err().map(|e| e.to_string())
+}
+
+/// Parse error of a station, with the offending character marked when known, e.g. 12+3[x]4.
+pub fn station_error(value: &str, equations: &StationEquations) -> Option<String> {
+    if value.trim().is_empty() {
+        return None;
+    }
+    let error = equations.parse(value).err()?;
+    let marked = match error.position() {
+        Some(position) => value
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match i == position {
+                true => format!("[{}]", c),
+                false => c.to_string(),
+            })
+            .collect::<String>(),
+        None => value.to_string(),
+    };
+
+    Some(format!("{} {}", marked.trim(), error))
+}
+
+/// True when every mandatory field is filled in and parses.
+pub fn fields_valid(fields: &[(&str, Option<String>)]) -> bool {
+    fields
+        .iter()
+        .all(|(value, error)| !value.trim().is_empty() && error.is_none())
+}
+
+/// Text input that is outlined, with its parse error under it, while the value doesn't parse.
+pub fn checked_input<'a>(
+    placeholder: &str,
+    value: &str,
+    on_input: impl Fn(String) -> Message + 'a,
+    error: Option<String>,
+) -> Column<'a, Message> {
+    let input = text_input(placeholder, value).on_input(on_input);
+
+    match error {
+        None => column![input].width(Length::Fill),
+        Some(error) => column![
+            input.style(theme::TextInput::Custom(Box::new(InvalidInput))),
+            text(error).size(14).style(Color::from(ERROR_COLOR)),
+        ]
+        .spacing(2)
+        .width(Length::Fill),
+    }
+}
+
+#[cfg(test)]
+mod validation_tests {
+    use super::{field_error, fields_valid, station_error};
+    use crate::datatypes::{coerce_length, StationEquations};
+
+    #[test]
+    fn field_errors() {
+        assert_eq!(field_error("", coerce_length), None);
+        assert_eq!(field_error("120", coerce_length), None);
+        assert!(field_error("12a", coerce_length).is_some());
+
+        let equations = StationEquations::default();
+        assert_eq!(station_error("12+34", &equations), None);
+        assert!(station_error("12+3x4", &equations)
+            .unwrap()
+            .starts_with("12+3[x]4"));
+
+        assert!(fields_valid(&[("12+34", None), ("100", None)]));
+        assert!(!fields_valid(&[("12+34", None), ("", None)]));
+        assert!(!fields_valid(&[("12+3x4", Some("bad".to_string()))]));
+    }
+}
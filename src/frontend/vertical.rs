@@ -1,13 +1,13 @@
 use iced::widget::{checkbox, column, text, Column};
 
-use crate::datatypes::coerce_station_value;
 use crate::frontend::*;
+use crate::tables::get_min_sight;
 use crate::vertical::calculate::{ObstacleReturn, VerticalCurve};
 
 pub fn vertical_header_group<'a>() -> Column<'a, Message> {
     let title = text("Vertical Curves (Pre-Release)")
         .width(Length::Fill)
-        .size(50)
+        .size(TITLE_SIZE)
         .style(Color::from([0.5, 0.5, 0.5]))
         .horizontal_alignment(alignment::Horizontal::Center);
     let switch = button("Switch to Horizontal Curve").on_press(Message::SwitchCurveType);
@@ -16,7 +16,7 @@ pub fn vertical_header_group<'a>() -> Column<'a, Message> {
 }
 
 impl VerticalData {
-    pub fn vertical_input_group(&self) -> Column<Message> {
+    pub fn vertical_input_group(&self) -> Column<'_, Message> {
         let mut column = column![]
             .spacing(10)
             .width(Length::FillPortion(2))
@@ -29,31 +29,89 @@ impl VerticalData {
         column
     }
 
+    /// Mandatory inputs paired with their parse errors.
+    fn mandatory_fields(&self) -> [(&str, Option<String>); 5] {
+        let equations = self.to_equations().unwrap_or_default();
+        let length = match self.input_length_method {
+            VerticalLengthDefinition::Length => &self.input_length,
+            VerticalLengthDefinition::K => &self.input_k_value,
+        };
+        [
+            (
+                &self.input_station,
+                station_error(&self.input_station, &equations),
+            ),
+            (
+                &self.input_elevation,
+                field_error(&self.input_elevation, coerce_elevation),
+            ),
+            (
+                &self.input_incoming_grade,
+                field_error(&self.input_incoming_grade, coerce_grade),
+            ),
+            (
+                &self.input_outgoing_grade,
+                field_error(&self.input_outgoing_grade, coerce_grade),
+            ),
+            (length, field_error(length, coerce_length)),
+        ]
+    }
+
+    pub fn mandatory_valid(&self) -> bool {
+        fields_valid(&self.mandatory_fields())
+    }
+
     fn mandatory_block(&self) -> Column<'_, Message> {
+        let [station, elevation, incoming, outgoing, length] = self.mandatory_fields().map(|x| x.1);
         column![
             subtitle("Inputs"),
             row![
                 text(format!("{:?} STA:", &self.input_method)),
-                text_input("Station (12+34)", &self.input_station).on_input(Message::StationModify),
+                checked_input(
+                    "Station (12+34)",
+                    &self.input_station,
+                    Message::StationModify,
+                    station
+                ),
                 text("ELEV:"),
-                text_input("Elevation (5678)", &self.input_elevation)
-                    .on_input(Message::ElevationModify),
+                checked_input(
+                    "Elevation (5678)",
+                    &self.input_elevation,
+                    Message::ElevationModify,
+                    elevation
+                ),
                 button(stext('S')).on_press(Message::InputMethodToggle)
             ]
             .spacing(H_S),
             row![
                 text("Grade In:"),
-                text_input("(0.01 = 1%)", &self.input_incoming_grade)
-                    .on_input(Message::IncomingGradeModify),
+                checked_input(
+                    "(0.01 = 1%)",
+                    &self.input_incoming_grade,
+                    Message::IncomingGradeModify,
+                    incoming
+                ),
                 text("Grade Out:"),
-                text_input("(-0.02 = -2%)", &self.input_outgoing_grade)
-                    .on_input(Message::OutgoingGradeModify),
+                checked_input(
+                    "(-0.02 = -2%)",
+                    &self.input_outgoing_grade,
+                    Message::OutgoingGradeModify,
+                    outgoing
+                ),
             ]
             .spacing(H_S),
-            row![
-                text("Length:"),
-                text_input("(100)", &self.input_length).on_input(Message::LengthModify)
-            ]
+            match self.input_length_method {
+                VerticalLengthDefinition::Length => row![
+                    text("Length:"),
+                    checked_input("(100)", &self.input_length, Message::LengthModify, length),
+                    button(cycle_icon()).on_press(Message::LengthMethodToggle)
+                ],
+                VerticalLengthDefinition::K => row![
+                    text("K:"),
+                    checked_input("(151)", &self.input_k_value, Message::KValueModify, length),
+                    button(cycle_icon()).on_press(Message::LengthMethodToggle)
+                ],
+            }
             .spacing(H_S)
         ]
         .spacing(H_S)
@@ -62,17 +120,58 @@ impl VerticalData {
     fn optional_block(&self) -> Column<'_, Message> {
         column![
             subtitle("Additional Details"),
+            format_row(),
             row![
                 text("Interval:"),
-                text_input("(00+25)", &self.input_station_interval)
-                    .on_input(Message::StationIntervalModify),
+                checked_input(
+                    "(00+25)",
+                    &self.input_station_interval,
+                    Message::StationIntervalModify,
+                    field_error(&self.input_station_interval, coerce_station_value)
+                ),
+            ]
+            .spacing(H_S),
+            row![
+                text("Equations:"),
+                checked_input(
+                    "(125+40.22 = 125+00; 150+00 = 151+00)",
+                    &self.input_station_equations,
+                    Message::StationEquationsModify,
+                    field_error(&self.input_station_equations, coerce_station_equations)
+                ),
             ]
             .spacing(H_S),
             row![
                 text("Design Speed:"),
-                text_input("(65)", &self.input_design_speed).on_input(Message::DesignSpeed),
+                checked_input(
+                    "(65)",
+                    &self.input_design_speed,
+                    Message::DesignSpeed,
+                    field_error(&self.input_design_speed, coerce_speed)
+                ),
+                button(stext('R')).on_press(Message::SpeedLookupToggle),
                 button(stext('A')).on_press(Message::DesignStandardToggle),
                 button(cycle_icon()).on_press(Message::SightTypeToggle),
+                button(exit_icon()).on_press(Message::LoadStandard),
+            ]
+            .spacing(H_S),
+            maneuver_row(self.input_sight_type),
+            error_row(&self.standard_error),
+            row![
+                text("Reaction Time:"),
+                checked_input(
+                    "(2.5 s)",
+                    &self.input_reaction_time,
+                    Message::ReactionTimeModify,
+                    field_error(&self.input_reaction_time, coerce_reaction_time)
+                ),
+                text("Deceleration:"),
+                checked_input(
+                    "(11.2 ft/s²)",
+                    &self.input_deceleration,
+                    Message::DecelerationModify,
+                    field_error(&self.input_deceleration, coerce_deceleration)
+                ),
             ]
             .spacing(H_S),
             checkbox(
@@ -83,15 +182,41 @@ impl VerticalData {
             .spacing(H_S),
             row![
                 text("Obstacles:"),
-                text_input("STA:", &self.input_obstacle_station).on_input(Message::ObstacleStation),
-                text_input("ELEV:", &self.input_obstacle_elevation)
-                    .on_input(Message::ObstacleElevation),
+                checked_input(
+                    "STA:",
+                    &self.input_obstacle_station,
+                    Message::ObstacleStation,
+                    station_error(
+                        &self.input_obstacle_station,
+                        &self.to_equations().unwrap_or_default()
+                    )
+                ),
+                checked_input(
+                    "ELEV:",
+                    &self.input_obstacle_elevation,
+                    Message::ObstacleElevation,
+                    field_error(&self.input_obstacle_elevation, coerce_elevation)
+                ),
                 button(Self::obs_type_toggle_arrow(self.input_obstacle_type))
                     .on_press(Message::ObstacleTypeToggle),
-                button(stext('+')).on_press(Message::AddObstacle),
-                button(stext('-')).on_press(Message::RemoveObstacle)
+                button(match self.selected_obstacle {
+                    Some(_) => good_check_icon(),
+                    None => stext('+'),
+                })
+                .on_press(Message::AddObstacle),
+                button(stext('-')).on_press(Message::RemoveObstacle),
+                button(exit_icon()).on_press(Message::ImportRows),
             ]
-            .spacing(H_S)
+            .spacing(H_S),
+            error_row(&self.list_error),
+            table(
+                self.obstacles
+                    .interval
+                    .iter()
+                    .map(|x| format!("{} {:?}", x.0, x.1))
+                    .collect(),
+                self.selected_obstacle
+            ),
         ]
         .spacing(H_S)
     }
@@ -103,7 +228,7 @@ impl VerticalData {
         }
     }
 
-    pub fn vertical_output_group(&self) -> Column<Message> {
+    pub fn vertical_output_group(&self) -> Column<'_, Message> {
         let h_s = 5;
         let mut column = column![]
             .spacing(10)
@@ -120,6 +245,8 @@ impl VerticalData {
                     .push(self.major_stations_block(&w))
                     .push(self.extreme_block(&w))
                     .push(self.validation_block(&w))
+                    .push(self.k_validation_block(&w))
+                    .push(self.grade_adjusted_block(&w))
                     .push(self.obstacle_block(&w))
                     .push(self.interval_block(&w));
             }
@@ -127,6 +254,46 @@ impl VerticalData {
         column
     }
 
+    /// Key dimensions and compliance results for the comparison view.
+    pub fn comparison_rows(&self) -> Result<Vec<(&'static str, String)>> {
+        let w = self.to_vertical_curve()?;
+        let dimensions = w.dimensions;
+        let mut rows = vec![
+            ("PVI", w.stations.pvi.to_input()),
+            ("Length", fmt_length(dimensions.curve_length)),
+            (
+                "Grades",
+                format!(
+                    "{:.2}% / {:.2}%",
+                    dimensions.incoming_grade * 100.0,
+                    dimensions.outgoing_grade * 100.0
+                ),
+            ),
+            ("K Value", fmt_k(dimensions.k_value)),
+            ("External", fmt_length(dimensions.external)),
+        ];
+        if self.input_design_speed.is_empty() {
+            rows.push(("Curve Length Check", "No speed given.".to_string()));
+            return Ok(rows);
+        }
+        let compliance = w.is_compliant(
+            self.input_design_standard,
+            self.input_sight_type,
+            self.input_speed_lookup,
+            calc_adjustment(self.sustained_downgrade),
+        );
+        rows.push((
+            "Curve Length Check",
+            compliance_text(compliance, dimensions.curve_length),
+        ));
+        let k_compliance = w.is_k_compliant(self.input_design_standard, self.input_speed_lookup);
+        rows.push((
+            "K Value Check",
+            compliance_text(k_compliance, dimensions.k_value.unwrap_or_default()),
+        ));
+        Ok(rows)
+    }
+
     fn curve_details_block(&self, w: &VerticalCurve) -> Column<'_, Message> {
         column![subtitle("Curve Details"), text(format!("{}", w.dimensions)),]
     }
@@ -158,16 +325,22 @@ impl VerticalData {
                             obstacle_column = obstacle_column.push(row![
                                 good_check_icon(),
                                 text(format!(
-                                    "Obstacle: {} {:?}\nActual: {}\nDelta: {:2}",
-                                    w.1 .0, w.1 .1, w.2, w.3
+                                    "Obstacle: {} {:?}\nActual: {}\nDelta: {}",
+                                    w.1 .0,
+                                    w.1 .1,
+                                    w.2,
+                                    fmt_elevation(w.3)
                                 ))
                             ]);
                         } else {
                             obstacle_column = obstacle_column.push(row![
                                 exclam_icon(),
                                 text(format!(
-                                    "Obstacle: {} {:?}\nActual: {}\nDelta: {:2}",
-                                    w.1 .0, w.1 .1, w.2, w.3
+                                    "Obstacle: {} {:?}\nActual: {}\nDelta: {}",
+                                    w.1 .0,
+                                    w.1 .1,
+                                    w.2,
+                                    fmt_elevation(w.3)
                                 ))
                             ]);
                         }
@@ -181,7 +354,7 @@ impl VerticalData {
 
     fn validation_block(&self, w: &VerticalCurve) -> Column<'_, Message> {
         let mut validation_column = column![subtitle(&format!(
-            "Curve Length ( {:?} - {:?} )",
+            "Curve Length ( {} - {:?} )",
             self.input_design_standard, self.input_sight_type
         ))];
 
@@ -192,6 +365,7 @@ impl VerticalData {
             match w.is_compliant(
                 self.input_design_standard,
                 self.input_sight_type,
+                self.input_speed_lookup,
                 calc_adjustment(self.sustained_downgrade),
             ) {
                 Err(e) => {
@@ -202,14 +376,142 @@ impl VerticalData {
                     if j.0 {
                         validation_column = validation_column.push(row![
                             good_check_icon(),
-                            text(format!(" {:.2} > {:.2}", w.dimensions.curve_length, j.1))
+                            text(format!(
+                                " {} > {}",
+                                fmt_length(w.dimensions.curve_length),
+                                fmt_length(j.1)
+                            ))
                         ]);
                     } else {
                         validation_column = validation_column.push(row![
                             exclam_icon(),
-                            text(format!(" {:.2} < {:.2}", w.dimensions.curve_length, j.1))
+                            text(format!(
+                                " {} < {}",
+                                fmt_length(w.dimensions.curve_length),
+                                fmt_length(j.1)
+                            ))
                         ]);
                     }
+                    validation_column = validation_column.push(text(format!(" {}", j.2)));
+                }
+            }
+        }
+
+        validation_column
+    }
+
+    fn k_validation_block(&self, w: &VerticalCurve) -> Column<'_, Message> {
+        let mut validation_column = column![subtitle(&format!(
+            "K Value ( {} )",
+            self.input_design_standard
+        ))];
+
+        if self.input_design_speed.is_empty() {
+            validation_column =
+                validation_column.push(row![notification_icon(), text(" No speed given.")]);
+        } else {
+            match w.is_k_compliant(self.input_design_standard, self.input_speed_lookup) {
+                Err(e) => {
+                    validation_column =
+                        validation_column.push(row![exclam_icon(), text(format!(" {}", e))]);
+                }
+                Ok(j) => {
+                    if j.0 {
+                        validation_column = validation_column.push(row![
+                            good_check_icon(),
+                            text(format!(
+                                " {} > {}",
+                                fmt_k(w.dimensions.k_value),
+                                fmt_length(j.1)
+                            ))
+                        ]);
+                    } else {
+                        validation_column = validation_column.push(row![
+                            exclam_icon(),
+                            text(format!(
+                                " {} < {}",
+                                fmt_k(w.dimensions.k_value),
+                                fmt_length(j.1)
+                            ))
+                        ]);
+                    }
+                    validation_column = validation_column.push(text(format!(" {}", j.2)));
+                }
+            }
+        }
+
+        validation_column
+    }
+
+    fn grade_adjusted_block(&self, w: &VerticalCurve) -> Column<'_, Message> {
+        let mut validation_column = column![subtitle(&format!(
+            "Grade-Adjusted Stopping Sight ( {} )",
+            self.input_design_standard
+        ))];
+
+        if self.input_design_speed.is_empty() {
+            validation_column =
+                validation_column.push(row![notification_icon(), text(" No speed given.")]);
+            return validation_column;
+        }
+
+        let (reaction_time, deceleration) = match (
+            coerce_reaction_time(&self.input_reaction_time),
+            coerce_deceleration(&self.input_deceleration),
+        ) {
+            (Err(e), _) | (_, Err(e)) => {
+                return validation_column.push(row![exclam_icon(), text(format!(" {}", e))]);
+            }
+            (Ok(t), Ok(a)) => (t, a),
+        };
+
+        let (ahead, back) = match w.grade_adjusted_sight(reaction_time, deceleration) {
+            Err(e) => {
+                return validation_column.push(row![exclam_icon(), text(format!(" {}", e))]);
+            }
+            Ok(sight) => sight,
+        };
+        validation_column = validation_column.push(text(format!(
+            " Ahead ({:.2}%): {}\n Back ({:.2}%): {}",
+            w.dimensions.outgoing_grade * 100.0,
+            fmt_length(ahead),
+            -w.dimensions.incoming_grade * 100.0,
+            fmt_length(back)
+        )));
+        if let Some(t) = get_min_sight(
+            w.dimensions.design_speed,
+            self.input_design_standard,
+            SightType::Stopping,
+            self.input_speed_lookup,
+        ) {
+            validation_column = validation_column.push(text(format!(" {}", t)));
+        }
+
+        match w.is_grade_adjusted_compliant(self.input_design_standard, reaction_time, deceleration)
+        {
+            Err(e) => {
+                validation_column =
+                    validation_column.push(row![exclam_icon(), text(format!(" {}", e))]);
+            }
+            Ok(j) => {
+                if j.0 {
+                    validation_column = validation_column.push(row![
+                        good_check_icon(),
+                        text(format!(
+                            " {} > {}",
+                            fmt_length(w.dimensions.curve_length),
+                            fmt_length(j.1)
+                        ))
+                    ]);
+                } else {
+                    validation_column = validation_column.push(row![
+                        exclam_icon(),
+                        text(format!(
+                            " {} < {}",
+                            fmt_length(w.dimensions.curve_length),
+                            fmt_length(j.1)
+                        ))
+                    ]);
                 }
             }
         }
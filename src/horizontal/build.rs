@@ -0,0 +1,140 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::horizontal::calculate::Error;
+use crate::horizontal::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveElement {
+    Radius,
+    CurveAngle,
+    Tangent,
+    Length,
+    LongChord,
+    MiddleOrdinate,
+    External,
+}
+
+impl fmt::Display for CurveElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Radius => "Radius",
+            Self::CurveAngle => "Curve Angle",
+            Self::Tangent => "Tangent",
+            Self::Length => "Curve Length",
+            Self::LongChord => "Long Chord",
+            Self::MiddleOrdinate => "Middle Ordinate",
+            Self::External => "External",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl CurveElement {
+    /// Element length for a unit radius, given half the curve angle. Written to stay accurate for small angles.
+    fn per_radius(self, half_angle: f64) -> f64 {
+        match self {
+            Self::Radius => 1.0,
+            Self::CurveAngle => 2.0 * half_angle,
+            Self::Tangent => half_angle.tan(),
+            Self::Length => 2.0 * half_angle,
+            Self::LongChord => 2.0 * half_angle.sin(),
+            Self::MiddleOrdinate => 2.0 * (half_angle / 2.0).sin().powi(2),
+            Self::External => 2.0 * (half_angle / 2.0).sin().powi(2) / half_angle.cos(),
+        }
+    }
+}
+
+/// Solves the radius and curve angle from any two curve elements. Curve lengths are arc lengths.
+/// When two curves fit (tangent & middle ordinate), the one with the smaller curve angle is returned.
+pub fn solve_elements(
+    first: (CurveElement, f64),
+    second: (CurveElement, f64),
+) -> Result<(f64, Angle), Error> {
+    if first.0 == second.0 {
+        return Err(Error::InconsistentElements);
+    }
+    if first.1 <= 0.0 || second.1 <= 0.0 || !first.1.is_finite() || !second.1.is_finite() {
+        return Err(Error::NonPositiveElement);
+    }
+
+    let rank = |e: CurveElement| match e {
+        CurveElement::CurveAngle => 0,
+        CurveElement::Radius => 1,
+        _ => 2,
+    };
+    let (first, second) = match rank(second.0) < rank(first.0) {
+        true => (second, first),
+        false => (first, second),
+    };
+
+    let half_angle = match (first.0, second.0) {
+        (CurveElement::CurveAngle, _) => {
+            check_curve_angle(first.1)?;
+            first.1 / 2.0
+        }
+        (CurveElement::Radius, e) => bisect(|h| e.per_radius(h), second.1 / first.1)?,
+        (a, b) => bisect(|h| b.per_radius(h) / a.per_radius(h), second.1 / first.1)?,
+    };
+    if half_angle <= 0.0 || half_angle >= PI {
+        return Err(Error::InconsistentElements);
+    }
+
+    let radius = match first.0 {
+        CurveElement::Radius => first.1,
+        CurveElement::CurveAngle => second.1 / second.0.per_radius(half_angle),
+        e => first.1 / e.per_radius(half_angle),
+    };
+    // past 180 degrees the tangents meet behind the curve, tangent and external turn negative
+    if radius <= 0.0 || !radius.is_finite() {
+        return Err(Error::InconsistentElements);
+    }
+
+    Ok((
+        radius,
+        Angle {
+            radians: half_angle * 2.0,
+        },
+    ))
+}
+
+/// Curve angles run between 0 and 360 degrees, except 180 where the tangents never meet.
+pub fn check_curve_angle(curve_angle: f64) -> Result<(), Error> {
+    if curve_angle <= 0.0 || curve_angle >= 2.0 * PI {
+        return Err(Error::NonPositiveElement);
+    }
+    if (curve_angle - PI).abs() < 1e-9 {
+        return Err(Error::ParallelTangents);
+    }
+    Ok(())
+}
+
+/// Finds the smallest half curve angle where `f` meets `target`.
+fn bisect(f: impl Fn(f64) -> f64, target: f64) -> Result<f64, Error> {
+    let steps = 1000;
+    let (start, end) = (1e-9, PI / 2.0 - 1e-9);
+    let below = |x: f64| f(x) < target;
+    let step = (end - start) / steps as f64;
+
+    let mut lo = start;
+    let bracket = (1..=steps).map(|i| start + step * i as f64).find(|hi| {
+        let found = below(lo) != below(*hi);
+        if !found {
+            lo = *hi;
+        }
+        found
+    });
+    let mut hi = bracket.ok_or(Error::InconsistentElements)?;
+
+    let rising = below(lo);
+    for _ in 0..100 {
+        let mid = (lo + hi) / 2.0;
+        if below(mid) == rising {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Ok((lo + hi) / 2.0)
+}
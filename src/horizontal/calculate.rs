@@ -1,7 +1,7 @@
 use anyhow::Result;
 
 use crate::horizontal::*;
-use crate::tables::get_min_sight;
+use crate::tables::{get_min_sight, TableLookup};
 
 #[derive(Debug, Clone, Copy)]
 pub struct HorizontalStations {
@@ -19,15 +19,21 @@ pub struct HorizontalDimensions {
     pub middle_ordinate: f64,
     pub external: f64,
     pub curve_length_100: Angle, // Da
+    pub degree_definition: DegreeDefinition,
     pub curve_angle: Angle,
+    pub direction: CurveDirection,
     pub design_speed: i32,
     pub sight_distance: f64,
+    pub hso: f64,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct HorizontalCurve {
     pub dimensions: HorizontalDimensions,
     pub stations: HorizontalStations,
+    pub coordinates: Option<CurveCoordinates>,
+    pub equations: StationEquations,
+    pub pins: Vec<f64>, // stations staked out along with the interval stations
 }
 
 impl HorizontalCurve {
@@ -35,14 +41,21 @@ impl HorizontalCurve {
         &self,
         design_standard: DesignStandard,
         sight_type: SightType,
+        speed_lookup: SpeedLookup,
         adjustment: f64,
-    ) -> Result<(bool, f64), Error> {
-        let min_sight = get_min_sight(self.dimensions.design_speed, design_standard, sight_type);
+    ) -> Result<(bool, f64, TableLookup), Error> {
+        let min_sight = get_min_sight(
+            self.dimensions.design_speed,
+            design_standard,
+            sight_type,
+            speed_lookup,
+        );
         match min_sight {
             None => Err(Error::DesignSpeedLUTError),
             Some(w) => Ok((
-                (self.dimensions.sight_distance >= w * adjustment),
-                w * adjustment,
+                (self.dimensions.sight_distance >= w.value * adjustment),
+                w.value * adjustment,
+                w,
             )),
         }
     }
@@ -54,4 +67,46 @@ pub enum Error {
     /// Design speed isn't specified in the manual.
     #[error("Design speed isn't specified in the manual.")]
     DesignSpeedLUTError,
+    /// Minimum radius couldn't be solved for the sight distance and clear distance.
+    #[error("Minimum radius couldn't be solved for the sight distance and clear distance.")]
+    HsoRadiusError,
+    /// Sightline offset is negative or larger than the curve's diameter.
+    #[error("Sightline offset (M) must be between zero and twice the radius.")]
+    HsoRange,
+    /// Chord definition requires a radius larger than 50.
+    #[error("Chord definition requires a radius larger than 50.")]
+    ChordRadius,
+    /// No curve fits both given elements.
+    #[error("Curve elements are inconsistent, no curve fits both values.")]
+    InconsistentElements,
+    /// Curve elements must be larger than zero.
+    #[error("Curve elements must be larger than zero.")]
+    NonPositiveElement,
+    /// Curve angle of 180 degrees leaves the tangents parallel.
+    #[error("Curve angle can't be 180 degrees, the tangents would be parallel.")]
+    ParallelTangents,
+    /// Curve angle direction doesn't match the bearings.
+    #[error("Curve angle direction doesn't match the bearings.")]
+    ConflictingDirection,
+    /// Bearings must deflect between 0 and 180 degrees.
+    #[error("Bearings must deflect between 0 and 180 degrees.")]
+    TangentBearings,
+    /// No curve tangent to both bearings passes through the point.
+    #[error("No curve tangent to both bearings passes through the point.")]
+    UnreachablePoint,
+    /// Curve coordinates aren't given.
+    #[error("PI coordinates and back bearing are required to locate the curve.")]
+    NoCoordinates,
+    /// Occupied point and backsight are the same point.
+    #[error("Occupied point and backsight must be different points.")]
+    CoincidentSetup,
+    /// Point doesn't project onto the tangents or the arc.
+    #[error("Point doesn't project onto the tangents or the curve.")]
+    NoProjection,
+    /// Radius is smaller than the design vehicle's wheelbases allow.
+    #[error("Radius is too small for the design vehicle to turn.")]
+    WideningRadius,
+    /// Offset reaches or passes the curve center.
+    #[error("Offset must be smaller than the radius on the inside of the curve.")]
+    OffsetRadius,
 }
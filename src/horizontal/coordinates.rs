@@ -0,0 +1,206 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::format::{format_settings, Label};
+use crate::horizontal::calculate::Error;
+use crate::horizontal::*;
+
+/// Curve located in plan by the PI coordinates and the back tangent bearing.
+#[derive(Debug, Clone, Copy)]
+pub struct CurveCoordinates {
+    pub pi: Point,
+    pub back_bearing: Angle,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MajorPoints {
+    pub pc: Point,
+    pub pi: Point,
+    pub pt: Point,
+    pub center: Point,
+}
+
+impl fmt::Display for MajorPoints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "PC > {}", self.pc)?;
+        writeln!(f, "PI > {}", self.pi)?;
+        writeln!(f, "PT > {}", self.pt)?;
+        writeln!(f, "Center > {}", self.center)?;
+        Ok(())
+    }
+}
+
+/// Point located relative to the curve, positive offsets are right of the direction of travel.
+#[derive(Debug, Clone, Copy)]
+pub struct PointLocation {
+    pub point: Point,
+    pub station: Station,
+    pub offset: f64,
+}
+
+impl fmt::Display for PointLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let settings = format_settings();
+        let side = match self.offset < 0.0 {
+            true => "LT",
+            false => "RT",
+        };
+        write!(
+            f,
+            "{} > {} {} {} {}",
+            self.point,
+            self.station,
+            settings.label(Label::Offset),
+            settings.length(self.offset.abs()),
+            side
+        )?;
+        Ok(())
+    }
+}
+
+/// Northing and easting components of a unit vector along the azimuth.
+pub fn unit(azimuth: Angle) -> (f64, f64) {
+    (azimuth.radians.cos(), azimuth.radians.sin())
+}
+
+/// Azimuth and horizontal distance between two points.
+pub fn inverse(from: Point, to: Point) -> (Angle, f64) {
+    let (dn, de) = (to.northing - from.northing, to.easting - from.easting);
+    (
+        Angle {
+            radians: de.atan2(dn).rem_euclid(2.0 * PI),
+        },
+        dn.hypot(de),
+    )
+}
+
+fn traverse(from: Point, azimuth: Angle, distance: f64) -> Point {
+    let (n, e) = unit(azimuth);
+    Point {
+        northing: from.northing + distance * n,
+        easting: from.easting + distance * e,
+    }
+}
+
+impl HorizontalCurve {
+    fn curve_coordinates(&self) -> Result<CurveCoordinates, Error> {
+        self.coordinates.ok_or(Error::NoCoordinates)
+    }
+
+    fn turn(&self) -> f64 {
+        self.dimensions.direction.turn()
+    }
+
+    /// Centerline point and direction of travel at a station. Stations off the curve lie on the tangents.
+    pub fn locate(&self, station: f64) -> Result<(Point, Angle), Error> {
+        let coordinates = self.curve_coordinates()?;
+        let back = coordinates.back_bearing;
+        let ahead = Angle {
+            radians: back.radians + self.turn() * self.dimensions.curve_angle.radians,
+        };
+        let pc = traverse(coordinates.pi, back, -self.dimensions.tangent);
+        let pt = traverse(coordinates.pi, ahead, self.dimensions.tangent);
+
+        if station <= self.stations.pc.value {
+            return Ok((traverse(pc, back, station - self.stations.pc.value), back));
+        }
+        if station >= self.stations.pt.value {
+            return Ok((traverse(pt, ahead, station - self.stations.pt.value), ahead));
+        }
+
+        let central = (station - self.stations.pc.value) / self.dimensions.curve_length
+            * self.dimensions.curve_angle.radians;
+        let heading = Angle {
+            radians: back.radians + self.turn() * central,
+        };
+        let right = PI / 2.0 * self.turn();
+        let center = traverse(
+            pc,
+            Angle {
+                radians: back.radians + right,
+            },
+            self.dimensions.radius,
+        );
+        let point = traverse(
+            center,
+            Angle {
+                radians: heading.radians - right,
+            },
+            self.dimensions.radius,
+        );
+
+        Ok((point, heading))
+    }
+
+    /// Point at a station and offset, positive offsets are right of the direction of travel.
+    pub fn point_at(&self, station: f64, offset: f64) -> Result<Point, Error> {
+        let (point, heading) = self.locate(station)?;
+        Ok(traverse(
+            point,
+            Angle {
+                radians: heading.radians + PI / 2.0,
+            },
+            offset,
+        ))
+    }
+
+    /// Station and signed offset of a point's perpendicular projection onto the back tangent, the arc or the ahead tangent.
+    /// When more than one projection exists, the closest is returned.
+    pub fn locate_point(&self, point: Point) -> Result<PointLocation, Error> {
+        let coordinates = self.curve_coordinates()?;
+        let points = self.major_points()?;
+        let back = coordinates.back_bearing;
+        let ahead = Angle {
+            radians: back.radians + self.turn() * self.dimensions.curve_angle.radians,
+        };
+        let project = |from: Point, azimuth: Angle| {
+            let (n, e) = unit(azimuth);
+            let (dn, de) = (point.northing - from.northing, point.easting - from.easting);
+            (dn * n + de * e, de * n - dn * e)
+        };
+
+        let mut candidates = vec![];
+        let (along, offset) = project(points.pc, back);
+        if along <= 0.0 {
+            candidates.push((self.stations.pc.value + along, offset));
+        }
+        let (along, offset) = project(points.pt, ahead);
+        if along >= 0.0 {
+            candidates.push((self.stations.pt.value + along, offset));
+        }
+        let (to_pc, _) = inverse(points.center, points.pc);
+        let (to_point, distance) = inverse(points.center, point);
+        let central = (self.turn() * (to_point.radians - to_pc.radians)).rem_euclid(2.0 * PI);
+        if (0.0..=self.dimensions.curve_angle.radians).contains(&central) && distance > 0.0 {
+            candidates.push((
+                self.stations.pc.value
+                    + central / self.dimensions.curve_angle.radians * self.dimensions.curve_length,
+                self.turn() * (self.dimensions.radius - distance),
+            ));
+        }
+
+        candidates
+            .into_iter()
+            .min_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .map(|(station, offset)| PointLocation {
+                point,
+                station: self.equations.tag(Station {
+                    value: station,
+                    ..Default::default()
+                }),
+                offset,
+            })
+            .ok_or(Error::NoProjection)
+    }
+
+    pub fn major_points(&self) -> Result<MajorPoints, Error> {
+        let coordinates = self.curve_coordinates()?;
+
+        Ok(MajorPoints {
+            pc: self.point_at(self.stations.pc.value, 0.0)?,
+            pi: coordinates.pi,
+            pt: self.point_at(self.stations.pt.value, 0.0)?,
+            center: self.point_at(self.stations.pc.value, self.turn() * self.dimensions.radius)?,
+        })
+    }
+}
@@ -1,17 +1,23 @@
 use std::fmt;
 
+use crate::format::fmt_length;
 use crate::horizontal::*;
 
 impl fmt::Display for HorizontalDimensions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Radius: {:.2}", self.radius)?;
-        writeln!(f, "Curve Length: {:.2}", self.curve_length)?;
-        writeln!(f, "Tangent: {:.2}", self.tangent)?;
-        writeln!(f, "Long Chord: {:.2}", self.long_chord)?;
-        writeln!(f, "Middle Ordinate: {:.2}", self.middle_ordinate)?;
-        writeln!(f, "External: {:.2}", self.external.abs())?;
+        writeln!(f, "Radius: {}", fmt_length(self.radius))?;
+        writeln!(f, "Curve Length: {}", fmt_length(self.curve_length))?;
+        writeln!(f, "Tangent: {}", fmt_length(self.tangent))?;
+        writeln!(f, "Long Chord: {}", fmt_length(self.long_chord))?;
+        writeln!(f, "Middle Ordinate: {}", fmt_length(self.middle_ordinate))?;
+        writeln!(f, "External: {}", fmt_length(self.external.abs()))?;
         writeln!(f, "Angle: {}", self.curve_angle)?;
-        writeln!(f, "Angle/100: {}", self.curve_length_100)?;
+        writeln!(f, "Direction: {}", self.direction)?;
+        writeln!(
+            f,
+            "Degree of Curve ({:?}): {}",
+            self.degree_definition, self.curve_length_100
+        )?;
         Ok(())
     }
 }
@@ -0,0 +1,106 @@
+use std::fmt;
+
+use eqsolver::single_variable::FDNewton;
+
+use crate::format::fmt_length;
+use crate::horizontal::calculate::Error;
+use crate::horizontal::*;
+
+/// Horizontal sightline offset needed for a sight distance, measured from the inside lane centerline.
+pub fn calc_hso(radius: f64, curve_length: f64, sight_distance: f64) -> f64 {
+    if sight_distance <= curve_length {
+        radius * (1.0 - (sight_distance / (2.0 * radius)).cos())
+    } else {
+        let theta = curve_length / (2.0 * radius);
+        radius * (1.0 - theta.cos()) + (sight_distance - curve_length) / 2.0 * theta.sin()
+    }
+}
+
+/// Sight distance available for a horizontal sightline offset, the inverse of `calc_hso`.
+pub fn calc_hso_sight(radius: f64, curve_length: f64, hso: f64) -> Result<f64, Error> {
+    // the sightline can't be offset past the far side of the circle
+    if !(0.0..=2.0 * radius).contains(&hso) {
+        return Err(Error::HsoRange);
+    }
+    let sight_distance = 2.0 * radius * ((radius - hso) / radius).acos();
+    if sight_distance <= curve_length {
+        return Ok(sight_distance);
+    }
+
+    let theta = curve_length / (2.0 * radius);
+    Ok(curve_length + 2.0 * (hso - radius * (1.0 - theta.cos())) / theta.sin())
+}
+
+/// Smallest radius that provides the sight distance with the given horizontal sightline offset.
+pub fn calc_hso_radius(curve_angle: Angle, sight_distance: f64, hso: f64) -> Result<f64, Error> {
+    if hso <= 0.0 || sight_distance <= 0.0 {
+        return Err(Error::HsoRadiusError);
+    }
+
+    let f = |radius: f64| calc_hso(radius, radius * curve_angle.radians, sight_distance) - hso;
+    let radius = FDNewton::new(f)
+        .solve(sight_distance.powi(2) / (8.0 * hso))
+        .map_err(|x| Error::HsoRadiusError)?;
+
+    match radius.is_finite() && radius > 0.0 {
+        true => Ok(radius),
+        false => Err(Error::HsoRadiusError),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HsoDetail {
+    pub curve_length: f64,
+    pub available_sight: f64,
+    pub hso: f64,
+    pub target_sight: f64,
+    pub required_hso: f64,
+    pub min_radius: Option<f64>,
+}
+
+impl fmt::Display for HsoDetail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let case = |s: f64| match s <= self.curve_length {
+            true => "S ≤ L",
+            false => "S > L",
+        };
+        writeln!(
+            f,
+            "Available Sight: {} ({})",
+            fmt_length(self.available_sight),
+            case(self.available_sight)
+        )?;
+        writeln!(
+            f,
+            "Required HSO: {} for S = {} ({})",
+            fmt_length(self.required_hso),
+            fmt_length(self.target_sight),
+            case(self.target_sight)
+        )?;
+        match self.min_radius {
+            Some(radius) => writeln!(
+                f,
+                "Minimum Radius: {} for HSO = {}",
+                fmt_length(radius),
+                fmt_length(self.hso)
+            )?,
+            None => writeln!(f, "Minimum Radius: {}", Error::HsoRadiusError)?,
+        }
+        Ok(())
+    }
+}
+
+impl HorizontalCurve {
+    pub fn hso_detail(&self, target_sight: f64) -> HsoDetail {
+        let dim = &self.dimensions;
+
+        HsoDetail {
+            curve_length: dim.curve_length,
+            available_sight: dim.sight_distance,
+            hso: dim.hso,
+            target_sight,
+            required_hso: calc_hso(dim.radius, dim.curve_length, target_sight),
+            min_radius: calc_hso_radius(dim.curve_angle, target_sight, dim.hso).ok(),
+        }
+    }
+}
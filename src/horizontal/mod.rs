@@ -1,16 +1,29 @@
 use anyhow::Result;
-use std::f64::consts::PI;
+use std::fmt;
 
 use crate::datatypes::*;
 use crate::export::*;
 
+pub mod build;
 pub mod calculate;
+pub mod coordinates;
 pub mod display;
+pub mod hso;
 pub mod interval;
+pub mod offset;
+pub mod stakeout;
+pub mod through;
+pub mod widening;
 
+use self::build::*;
 use self::calculate::*;
+use self::coordinates::*;
+use self::hso::*;
+use self::stakeout::*;
+use self::through::*;
+use self::widening::*;
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum HorizontalStationDefinition {
     #[default]
     PI,
@@ -28,23 +41,157 @@ impl HorizontalStationDefinition {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum HorizontalBuildDefinition {
     #[default]
     RadiusCurveAngle,
     RadiusTangent,
+    DegreeCurveAngle,
+    RadiusLength,
+    RadiusLongChord,
+    RadiusMiddleOrdinate,
+    RadiusExternal,
+    TangentCurveAngle,
+    LengthCurveAngle,
+    LongChordCurveAngle,
+    MiddleOrdinateCurveAngle,
+    ExternalCurveAngle,
+    TangentLength,
+    TangentLongChord,
+    TangentMiddleOrdinate,
+    TangentExternal,
+    LengthLongChord,
+    LengthMiddleOrdinate,
+    LengthExternal,
+    LongChordMiddleOrdinate,
+    LongChordExternal,
+    MiddleOrdinateExternal,
+    ThroughPoint,
 }
 
 impl HorizontalBuildDefinition {
+    pub const ALL: [Self; 23] = [
+        Self::RadiusCurveAngle,
+        Self::RadiusTangent,
+        Self::DegreeCurveAngle,
+        Self::RadiusLength,
+        Self::RadiusLongChord,
+        Self::RadiusMiddleOrdinate,
+        Self::RadiusExternal,
+        Self::TangentCurveAngle,
+        Self::LengthCurveAngle,
+        Self::LongChordCurveAngle,
+        Self::MiddleOrdinateCurveAngle,
+        Self::ExternalCurveAngle,
+        Self::TangentLength,
+        Self::TangentLongChord,
+        Self::TangentMiddleOrdinate,
+        Self::TangentExternal,
+        Self::LengthLongChord,
+        Self::LengthMiddleOrdinate,
+        Self::LengthExternal,
+        Self::LongChordMiddleOrdinate,
+        Self::LongChordExternal,
+        Self::MiddleOrdinateExternal,
+        Self::ThroughPoint,
+    ];
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL
+            .iter()
+            .position(|x| *x == self)
+            .unwrap_or_default();
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Curve elements given by the build method, `None` for degree of curve and fixed points.
+    pub fn elements(self) -> Option<(CurveElement, CurveElement)> {
+        match self {
+            Self::RadiusCurveAngle => Some((CurveElement::Radius, CurveElement::CurveAngle)),
+            Self::RadiusTangent => Some((CurveElement::Radius, CurveElement::Tangent)),
+            Self::DegreeCurveAngle => None,
+            Self::RadiusLength => Some((CurveElement::Radius, CurveElement::Length)),
+            Self::RadiusLongChord => Some((CurveElement::Radius, CurveElement::LongChord)),
+            Self::RadiusMiddleOrdinate => {
+                Some((CurveElement::Radius, CurveElement::MiddleOrdinate))
+            }
+            Self::RadiusExternal => Some((CurveElement::Radius, CurveElement::External)),
+            Self::TangentCurveAngle => Some((CurveElement::Tangent, CurveElement::CurveAngle)),
+            Self::LengthCurveAngle => Some((CurveElement::Length, CurveElement::CurveAngle)),
+            Self::LongChordCurveAngle => Some((CurveElement::LongChord, CurveElement::CurveAngle)),
+            Self::MiddleOrdinateCurveAngle => {
+                Some((CurveElement::MiddleOrdinate, CurveElement::CurveAngle))
+            }
+            Self::ExternalCurveAngle => Some((CurveElement::External, CurveElement::CurveAngle)),
+            Self::TangentLength => Some((CurveElement::Tangent, CurveElement::Length)),
+            Self::TangentLongChord => Some((CurveElement::Tangent, CurveElement::LongChord)),
+            Self::TangentMiddleOrdinate => {
+                Some((CurveElement::Tangent, CurveElement::MiddleOrdinate))
+            }
+            Self::TangentExternal => Some((CurveElement::Tangent, CurveElement::External)),
+            Self::LengthLongChord => Some((CurveElement::Length, CurveElement::LongChord)),
+            Self::LengthMiddleOrdinate => {
+                Some((CurveElement::Length, CurveElement::MiddleOrdinate))
+            }
+            Self::LengthExternal => Some((CurveElement::Length, CurveElement::External)),
+            Self::LongChordMiddleOrdinate => {
+                Some((CurveElement::LongChord, CurveElement::MiddleOrdinate))
+            }
+            Self::LongChordExternal => Some((CurveElement::LongChord, CurveElement::External)),
+            Self::MiddleOrdinateExternal => {
+                Some((CurveElement::MiddleOrdinate, CurveElement::External))
+            }
+            Self::ThroughPoint => None,
+        }
+    }
+}
+
+impl fmt::Display for HorizontalBuildDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        match (self, self.elements()) {
+            (_, Some((a, b))) => write!(f, "{} & {}", a, b),
+            (Self::ThroughPoint, None) => write!(f, "Bearings & Fixed Point"),
+            (_, None) => write!(f, "Degree of Curve & Curve Angle"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DegreeDefinition {
+    #[default]
+    Arc, // D = 5729.58/R, highway
+    Chord, // sin(D/2) = 50/R, railroad
+}
+
+impl DegreeDefinition {
     pub fn next(self) -> Self {
         match self {
-            Self::RadiusCurveAngle => Self::RadiusTangent,
-            Self::RadiusTangent => Self::RadiusCurveAngle,
+            Self::Arc => Self::Chord,
+            Self::Chord => Self::Arc,
+        }
+    }
+
+    pub fn to_degree(self, radius: f64) -> Result<Angle, calculate::Error> {
+        match self {
+            Self::Arc => Ok(Angle {
+                radians: 100.0 / radius,
+            }),
+            Self::Chord if radius > 50.0 => Ok(Angle {
+                radians: 2.0 * (50.0 / radius).asin(),
+            }),
+            Self::Chord => Err(calculate::Error::ChordRadius),
+        }
+    }
+
+    pub fn to_radius(self, degree: Angle) -> f64 {
+        match self {
+            Self::Arc => 100.0 / degree.radians,
+            Self::Chord => 50.0 / (degree.radians / 2.0).sin(),
         }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct HorizontalData {
     pub input_directory: String,
     pub success_flags: [ExportSuccess; 2],
@@ -55,69 +202,78 @@ pub struct HorizontalData {
     pub input_radius: String,
     pub input_curve_angle: String,
     pub input_tangent: String,
+    pub input_degree: String,
+    pub input_long_chord: String,
+    pub input_middle_ordinate: String,
+    pub input_external: String,
+    pub input_degree_definition: DegreeDefinition,
+    pub input_direction: CurveDirection,
+    pub input_pi_point: String,
+    pub input_back_bearing: String,
+    pub input_ahead_bearing: String,
+    pub input_point_method: PointDefinition,
+    pub input_point: String,
     pub input_station_interval: String,
+    pub input_station_equations: String,
+    pub input_stakeout_method: StakeoutMethod,
+    pub input_setup_method: SetupDefinition,
+    pub input_occupied: String,
+    pub input_backsight: String,
+    pub input_locate_points: String,
     pub input_sight_type: SightType,
     pub input_design_speed: String,
+    pub input_speed_lookup: SpeedLookup,
     pub input_m: String,
     pub input_design_standard: DesignStandard,
+    pub standard_error: String,
     pub sustained_downgrade: bool,
+    pub input_offsets: String,
+    pub input_design_vehicle: DesignVehicle,
+    pub input_lanes: String,
+    pub input_traveled_way: String,
     pub input_pin_station: String,
     pub pin: CurveDetail,
+    pub selected_pin: Option<usize>,
+    pub list_error: String,
 }
 
 impl HorizontalData {
     fn to_dimensions(&self) -> Result<HorizontalDimensions> {
-        let (
-            radius,
-            curve_angle,
-            curve_length,
-            tangent,
-            external,
-            middle_ordinate,
-            long_chord,
-            curve_length_100,
-            design_speed,
-            sight_distance,
-        );
-
-        match self.input_build_method {
-            HorizontalBuildDefinition::RadiusCurveAngle => {
-                radius = coerce_length(&self.input_radius)?;
-                curve_angle = Angle::from(self.input_curve_angle.as_str())?;
-
-                curve_length = radius * curve_angle.to_decimal_degrees() * PI / 180.0;
-                tangent = radius * (curve_angle.radians / 2.0).tan();
-                external = radius * (1.0 / (curve_angle.radians / 2.0).cos() - 1.0);
-                middle_ordinate = radius * (1.0 - (curve_angle.radians / 2.0).cos());
-                long_chord = 2.0 * radius * (curve_angle.radians / 2.0).sin();
-                curve_length_100 = Angle {
-                    radians: 5729.6 / radius * PI / 180.0,
-                };
-                let m = coerce_length(&self.input_m).unwrap_or_default();
-
-                design_speed = coerce_speed(&self.input_design_speed).unwrap_or_default();
-                sight_distance = radius / 28.65 * ((radius - m) / radius).acos() * 180.0 / PI;
+        let (radius, curve_angle) = match self.input_build_method.elements() {
+            Some((first, second)) => solve_elements(
+                (first, self.element_value(first)?),
+                (second, self.element_value(second)?),
+            )?,
+            None if self.input_build_method == HorizontalBuildDefinition::ThroughPoint => {
+                self.solve_through_point()?
             }
-            HorizontalBuildDefinition::RadiusTangent => {
-                tangent = coerce_length(&self.input_tangent)?;
-                radius = coerce_length(&self.input_radius)?;
-
-                curve_angle = Angle {
-                    radians: (tangent / radius).atan() * 2.,
-                };
-                curve_length = radius * curve_angle.to_decimal_degrees() * PI / 180.0;
-                external = radius * (1.0 / (curve_angle.radians / 2.0).cos() - 1.0);
-                middle_ordinate = radius * (1.0 - (curve_angle.radians / 2.0).cos());
-                long_chord = 2.0 * radius * (curve_angle.radians / 2.0).sin();
-                curve_length_100 = Angle {
-                    radians: 5729.6 / radius * PI / 180.0,
-                };
-                let m = coerce_length(&self.input_m).unwrap_or_default();
-
-                design_speed = coerce_speed(&self.input_design_speed).unwrap_or_default();
-                sight_distance = radius / 28.65 * ((radius - m) / radius).acos() * 180.0 / PI;
-            } // _ => return Err(anyhow!("This method hasn't been implimented.")),
-        }
+            None => {
+                let (curve_angle, _) = Angle::from_directed(&self.input_curve_angle)?;
+                check_curve_angle(curve_angle.radians)?;
+                let radius = self
+                    .input_degree_definition
+                    .to_radius(Angle::from(self.input_degree.as_str())?);
+                if radius <= 0.0 || !radius.is_finite() {
+                    return Err(calculate::Error::NonPositiveElement.into());
+                }
+                (radius, curve_angle)
+            }
+        };
+        let direction = self.to_direction()?;
+
+        let degree_definition = self.input_degree_definition;
+        let curve_length_100 = degree_definition.to_degree(radius)?;
+        let curve_length = match degree_definition {
+            DegreeDefinition::Arc => radius * curve_angle.radians,
+            DegreeDefinition::Chord => 100.0 * curve_angle.radians / curve_length_100.radians,
+        };
+        let tangent = radius * (curve_angle.radians / 2.0).tan();
+        let external = radius * (1.0 / (curve_angle.radians / 2.0).cos() - 1.0);
+        let middle_ordinate = radius * (1.0 - (curve_angle.radians / 2.0).cos());
+        let long_chord = 2.0 * radius * (curve_angle.radians / 2.0).sin();
+        let hso = coerce_length(&self.input_m).unwrap_or_default();
+        let design_speed = coerce_speed(&self.input_design_speed).unwrap_or_default();
+        let sight_distance = calc_hso_sight(radius, curve_length, hso)?;
 
         Ok(HorizontalDimensions {
             radius,
@@ -127,15 +283,85 @@ impl HorizontalData {
             middle_ordinate,
             external,
             curve_length_100,
+            degree_definition,
             curve_angle,
+            direction,
             design_speed,
             sight_distance,
+            hso,
+        })
+    }
+
+    fn solve_through_point(&self) -> Result<(f64, Angle)> {
+        let pi = match self.input_pi_point.trim().is_empty() {
+            true => Point::default(),
+            false => coerce_point(&self.input_pi_point)?,
+        };
+        let back = coerce_bearing(&self.input_back_bearing)?;
+        let ahead = coerce_bearing(&self.input_ahead_bearing)?;
+        let point = match self.input_point_method {
+            PointDefinition::Coordinates => coerce_point(&self.input_point)?,
+            PointDefinition::DistanceOffset => {
+                let values = coerce_point(&self.input_point)?;
+                point_from_pi(pi, back, ahead, values.northing, values.easting)
+            }
+        };
+
+        Ok(solve_through_point(pi, back, ahead, point)?)
+    }
+
+    fn element_value(&self, element: CurveElement) -> Result<f64> {
+        Ok(match element {
+            CurveElement::Radius => coerce_length(&self.input_radius)?,
+            CurveElement::CurveAngle => Angle::from_directed(&self.input_curve_angle)?.0.radians,
+            CurveElement::Tangent => coerce_length(&self.input_tangent)?,
+            CurveElement::Length => coerce_length(&self.input_length)?,
+            CurveElement::LongChord => coerce_length(&self.input_long_chord)?,
+            CurveElement::MiddleOrdinate => coerce_length(&self.input_middle_ordinate)?,
+            CurveElement::External => coerce_length(&self.input_external)?,
         })
     }
 
-    fn to_stations(&self, dimensions: &HorizontalDimensions) -> Result<HorizontalStations> {
+    /// Direction from a signed or RT/LT curve angle, or from the bearings when both are given, otherwise the direction toggle.
+    fn to_direction(&self) -> Result<CurveDirection> {
+        let uses_angle = match self.input_build_method.elements() {
+            Some((first, second)) => {
+                first == CurveElement::CurveAngle || second == CurveElement::CurveAngle
+            }
+            None => self.input_build_method == HorizontalBuildDefinition::DegreeCurveAngle,
+        };
+        let from_angle = match uses_angle {
+            true => Angle::from_directed(&self.input_curve_angle)?.1,
+            false => None,
+        };
+        let from_bearings = match (
+            coerce_bearing(&self.input_back_bearing),
+            coerce_bearing(&self.input_ahead_bearing),
+        ) {
+            (Ok(back), Ok(ahead)) => Some(match calc_deflection(back, ahead) < 0.0 {
+                true => CurveDirection::Left,
+                false => CurveDirection::Right,
+            }),
+            _ => None,
+        };
+
+        match (from_angle, from_bearings) {
+            (Some(a), Some(b)) if a != b => Err(calculate::Error::ConflictingDirection.into()),
+            (a, b) => Ok(a.or(b).unwrap_or(self.input_direction)),
+        }
+    }
+
+    pub fn to_equations(&self) -> Result<StationEquations> {
+        Ok(coerce_station_equations(&self.input_station_equations)?)
+    }
+
+    fn to_stations(
+        &self,
+        dimensions: &HorizontalDimensions,
+        equations: &StationEquations,
+    ) -> Result<HorizontalStations> {
         let starting_station = Station {
-            value: coerce_station_value(&self.input_station)?,
+            value: equations.parse(&self.input_station)?,
             ..Default::default()
         };
 
@@ -157,7 +383,11 @@ impl HorizontalData {
             },
         };
 
-        return Ok(stations);
+        Ok(HorizontalStations {
+            pc: equations.tag(stations.pc),
+            pi: equations.tag(stations.pi),
+            pt: equations.tag(stations.pt),
+        })
     }
 
     fn pc_to_pi(&self, sts: Station, dim: &HorizontalDimensions) -> Station {
@@ -183,7 +413,7 @@ impl HorizontalData {
 
     fn pi_to_pt(&self, sts: Station, dim: &HorizontalDimensions) -> Station {
         Station {
-            value: sts.value + dim.tangent,
+            value: sts.value - dim.tangent + dim.curve_length,
             ..Default::default()
         }
     }
@@ -197,25 +427,502 @@ impl HorizontalData {
 
     fn pt_to_pi(&self, sts: Station, dim: &HorizontalDimensions) -> Station {
         Station {
-            value: sts.value - dim.tangent,
+            value: sts.value - dim.curve_length + dim.tangent,
             ..Default::default()
         }
     }
 
+    pub fn to_widening(&self, curve: &HorizontalCurve) -> Result<Widening> {
+        let lanes = match self.input_lanes.trim().is_empty() {
+            true => 2,
+            false => coerce_lanes(&self.input_lanes)?,
+        };
+        let width_on_tangent = coerce_length(&self.input_traveled_way)?;
+
+        Ok(curve.widening(self.input_design_vehicle, lanes, width_on_tangent)?)
+    }
+
+    /// Curve coordinates when the PI and back bearing are given. The ahead bearing only sets the direction, see `to_direction`.
+    fn to_coordinates(&self) -> Result<Option<CurveCoordinates>> {
+        if self.input_pi_point.trim().is_empty() || self.input_back_bearing.trim().is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(CurveCoordinates {
+            pi: coerce_point(&self.input_pi_point)?,
+            back_bearing: coerce_bearing(&self.input_back_bearing)?,
+        }))
+    }
+
+    fn to_setup_point(&self, curve: &HorizontalCurve, raw_data: &str) -> Result<Point> {
+        Ok(match self.input_setup_method {
+            SetupDefinition::Coordinates => coerce_point(raw_data)?,
+            SetupDefinition::StationOffset => {
+                let (station, offset) = coerce_station_offset(raw_data, &curve.equations)?;
+                curve.point_at(station, offset)?
+            }
+        })
+    }
+
+    pub fn to_stakeout(
+        &self,
+        curve: &HorizontalCurve,
+        station_interval: f64,
+    ) -> Result<CurveDetail> {
+        match self.input_stakeout_method {
+            StakeoutMethod::OccupiedPoint => Ok(curve.setup_stations(
+                self.to_setup_point(curve, &self.input_occupied)?,
+                self.to_setup_point(curve, &self.input_backsight)?,
+                station_interval,
+            )?),
+            method => Ok(curve.stakeout_stations(method, station_interval)),
+        }
+    }
+
     pub fn to_horizontal_curve(&self) -> Result<HorizontalCurve> {
         let dimensions = self.to_dimensions()?;
-        let stations = self.to_stations(&dimensions)?;
+        let equations = self.to_equations()?;
+        let stations = self.to_stations(&dimensions, &equations)?;
+        let coordinates = self.to_coordinates()?;
 
         Ok(HorizontalCurve {
             dimensions,
             stations,
+            coordinates,
+            equations,
+            pins: self.pin.interval.iter().map(|x| x.value).collect(),
         })
     }
 }
 
 #[cfg(test)]
 mod hori_tests {
+    use super::hso::{calc_hso, calc_hso_radius, calc_hso_sight};
     use super::HorizontalData;
+    use crate::datatypes::{Angle, CurveDirection, Station};
+
+    #[test]
+    fn hso_round_trip() {
+        // S <= L
+        let hso = calc_hso(1000., 800., 500.);
+        assert!((hso - 31.09).abs() < 0.01);
+        assert!((calc_hso_sight(1000., 800., hso).unwrap() - 500.).abs() < 1e-6);
+        // S > L
+        let hso = calc_hso(1000., 300., 500.);
+        assert!(hso < calc_hso(1000., 800., 500.));
+        assert!((calc_hso_sight(1000., 300., hso).unwrap() - 500.).abs() < 1e-6);
+        // offsets past the far side of the circle have no sightline
+        assert!(calc_hso_sight(1000., 800., 2000.).is_ok());
+        assert!(calc_hso_sight(1000., 800., 2000.1).is_err());
+        assert!(calc_hso_sight(1000., 800., -1.).is_err());
+
+        let curve_angle = Angle::from("40").unwrap();
+        let radius = calc_hso_radius(curve_angle, 500., 31.09).unwrap();
+        assert!((radius - 1000.).abs() < 1.);
+    }
+
+    #[test]
+    fn build_from_any_pair() {
+        let base = HorizontalData {
+            input_station: "100+00".to_string(),
+            input_radius: "1000".to_string(),
+            input_curve_angle: "40".to_string(),
+            ..Default::default()
+        };
+        let expected = base.to_horizontal_curve().unwrap().dimensions;
+        let filled = HorizontalData {
+            input_tangent: expected.tangent.to_string(),
+            input_length: expected.curve_length.to_string(),
+            input_long_chord: expected.long_chord.to_string(),
+            input_middle_ordinate: expected.middle_ordinate.to_string(),
+            input_external: expected.external.to_string(),
+            ..base
+        };
+
+        for method in super::HorizontalBuildDefinition::ALL {
+            if method.elements().is_none() {
+                continue;
+            }
+            let data = HorizontalData {
+                input_build_method: method,
+                ..filled.clone()
+            };
+            let dimensions = data.to_horizontal_curve().unwrap().dimensions;
+            assert!((dimensions.radius - 1000.).abs() < 1e-6, "{}", method);
+            assert!(
+                (dimensions.curve_angle.radians - expected.curve_angle.radians).abs() < 1e-9,
+                "{}",
+                method
+            );
+        }
+    }
+
+    #[test]
+    fn build_rejects_inconsistent_pair() {
+        let data = HorizontalData {
+            input_build_method: super::HorizontalBuildDefinition::RadiusLongChord,
+            input_station: "100+00".to_string(),
+            input_radius: "100".to_string(),
+            input_long_chord: "250".to_string(),
+            ..Default::default()
+        };
+        assert!(data.to_horizontal_curve().is_err());
+
+        let data = HorizontalData {
+            input_build_method: super::HorizontalBuildDefinition::MiddleOrdinateExternal,
+            input_station: "100+00".to_string(),
+            input_middle_ordinate: "20".to_string(),
+            input_external: "10".to_string(),
+            ..Default::default()
+        };
+        assert!(data.to_horizontal_curve().is_err());
+    }
+
+    #[test]
+    fn through_fixed_point() {
+        let base = HorizontalData {
+            input_station: "100+00".to_string(),
+            input_radius: "1000".to_string(),
+            input_curve_angle: "40".to_string(),
+            ..Default::default()
+        };
+        let expected = base.to_horizontal_curve().unwrap().dimensions;
+
+        // curve midpoint, along the bisector from the PI
+        let bisector = (10.0 + 90.0 + 20.0_f64).to_radians();
+        let data = HorizontalData {
+            input_build_method: super::HorizontalBuildDefinition::ThroughPoint,
+            input_pi_point: "5000, 5000".to_string(),
+            input_back_bearing: "N 10 E".to_string(),
+            input_ahead_bearing: "50".to_string(),
+            input_point: format!(
+                "{}, {}",
+                5000. + expected.external * bisector.cos(),
+                5000. + expected.external * bisector.sin()
+            ),
+            ..base.clone()
+        };
+        let dimensions = data.to_horizontal_curve().unwrap().dimensions;
+        assert!((dimensions.radius - 1000.).abs() < 1e-6);
+        assert!((dimensions.curve_angle.to_decimal_degrees() - 40.).abs() < 1e-9);
+
+        // the PC, located back along the tangent
+        let data = HorizontalData {
+            input_point_method: super::through::PointDefinition::DistanceOffset,
+            input_point: format!("{}, 0", expected.tangent),
+            ..data
+        };
+        let dimensions = data.to_horizontal_curve().unwrap().dimensions;
+        assert!((dimensions.radius - 1000.).abs() < 1e-6);
+
+        let data = HorizontalData {
+            input_point: "100, -5".to_string(),
+            ..data
+        };
+        assert!(data.to_horizontal_curve().is_err());
+    }
+
+    #[test]
+    fn stakeout_methods() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_station: "100+00".to_string(),
+            input_radius: "1000".to_string(),
+            input_curve_angle: "40".to_string(),
+            ..Default::default()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        let dimensions = curve.dimensions;
+        let deflection = curve.interval_stations(50.);
+
+        let from_pc = curve.stakeout_stations(super::StakeoutMethod::TangentOffsetPC, 50.);
+        let from_pt = curve.stakeout_stations(super::StakeoutMethod::TangentOffsetPT, 50.);
+        let chord = curve.stakeout_stations(super::StakeoutMethod::ChordOffset, 50.);
+        assert_eq!(deflection.interval.len(), from_pc.interval.len());
+        assert_eq!(deflection.interval.len(), chord.interval.len());
+
+        // 50 ft of arc on a 1000 ft radius, past the PC
+        let first = from_pc.interval[1];
+        assert_eq!(first.deflection, None);
+        assert!((first.distance.unwrap() - 1000. * 0.05_f64.sin()).abs() < 1e-9);
+        assert!((first.offset.unwrap() - 1000. * (1. - 0.05_f64.cos())).abs() < 1e-9);
+
+        // the PT sits on the ahead tangent at the PT and on the long chord
+        let last = from_pt.interval.last().unwrap();
+        assert!(last.distance.unwrap().abs() < 1e-9 && last.offset.unwrap().abs() < 1e-9);
+        let last = chord.interval.last().unwrap();
+        assert!((last.distance.unwrap() - dimensions.long_chord).abs() < 1e-9);
+        assert!(last.offset.unwrap().abs() < 1e-9);
+        assert!(chord
+            .interval
+            .iter()
+            .all(|x| x.offset.unwrap() <= dimensions.middle_ordinate + 1e-9));
+    }
+
+    #[test]
+    fn pinned_stations() {
+        let mut horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_station: "100+00".to_string(),
+            input_radius: "1000".to_string(),
+            input_curve_angle: "40".to_string(),
+            ..Default::default()
+        };
+        // on the curve, on an interval station and before the PC
+        for value in [10012.5, 10050., 9990.] {
+            horizontal_data.pin.interval.push(Station {
+                value,
+                ..Default::default()
+            });
+        }
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        let detail = curve.interval_stations(50.);
+        let values: Vec<f64> = detail.interval.iter().map(|x| x.value).collect();
+        assert_eq!(&values[..4], &[10000., 10012.5, 10050., 10100.]);
+
+        let pin = detail.interval[1];
+        let half_angle_per_length =
+            curve.dimensions.curve_angle.radians / curve.dimensions.curve_length / 2.;
+        assert!((pin.deflection.unwrap().radians - 12.5 * half_angle_per_length).abs() < 1e-12);
+        // chords are measured from the pin
+        let chord = 2. * 1000. * (37.5 * half_angle_per_length).sin();
+        assert!((detail.interval[2].chord.unwrap() - chord).abs() < 1e-9);
+    }
+
+    #[test]
+    fn occupied_point_stakeout() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_station: "100+00".to_string(),
+            input_radius: "1000".to_string(),
+            input_curve_angle: "40".to_string(),
+            input_pi_point: "5000, 5000".to_string(),
+            input_back_bearing: "0".to_string(),
+            input_ahead_bearing: "N 40d E".to_string(),
+            input_stakeout_method: super::StakeoutMethod::OccupiedPoint,
+            input_occupied: "5000, 5000".to_string(),
+            input_backsight: "6000, 5000".to_string(),
+            ..Default::default()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        let points = curve.major_points().unwrap();
+        assert!((points.center.northing - (5000. - curve.dimensions.tangent)).abs() < 1e-6);
+        assert!((points.center.easting - 6000.).abs() < 1e-6);
+        let (azimuth, distance) = super::coordinates::inverse(points.pi, points.pt);
+        assert!((azimuth.to_decimal_degrees() - 40.).abs() < 1e-9);
+        assert!((distance - curve.dimensions.tangent).abs() < 1e-6);
+
+        // from the PI, backsighting ahead on the back tangent, the PC is behind
+        let detail = horizontal_data.to_stakeout(&curve, 50.).unwrap();
+        let pc = detail.interval[0];
+        assert!((pc.deflection.unwrap().to_decimal_degrees() - 180.).abs() < 1e-6);
+        assert!((pc.distance.unwrap() - curve.dimensions.tangent).abs() < 1e-6);
+
+        // occupying the PC by station and backsighting down the back tangent, the curve deflections plus 180
+        let from_pc = HorizontalData {
+            input_setup_method: super::SetupDefinition::StationOffset,
+            input_occupied: "100+00, 0".to_string(),
+            input_backsight: "99+00, 0".to_string(),
+            ..horizontal_data
+        };
+        let detail = from_pc.to_stakeout(&curve, 50.).unwrap();
+        let deflection = curve.interval_stations(50.);
+        for (a, b) in detail
+            .interval
+            .iter()
+            .zip(deflection.interval.iter())
+            .skip(1)
+        {
+            let turned = a.deflection.unwrap().radians - b.deflection.unwrap().radians;
+            assert!((turned - std::f64::consts::PI).abs() < 1e-9);
+            let chord_from_pc = 2. * 1000. * b.deflection.unwrap().radians.sin();
+            assert!((a.distance.unwrap() - chord_from_pc).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn locate_points() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_station: "100+00".to_string(),
+            input_radius: "1000".to_string(),
+            input_curve_angle: "40".to_string(),
+            input_pi_point: "5000, 5000".to_string(),
+            input_back_bearing: "0".to_string(),
+            input_ahead_bearing: "320".to_string(),
+            ..Default::default()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+
+        // round trip on the back tangent, the arc and the ahead tangent, both sides
+        for (station, offset) in [(9950., 12.), (10300., -20.), (10300., 35.), (10800., -7.5)] {
+            let point = curve.point_at(station, offset).unwrap();
+            let location = curve.locate_point(point).unwrap();
+            assert!((location.station.value - station).abs() < 1e-6);
+            assert!((location.offset - offset).abs() < 1e-6);
+        }
+
+        // curve turns left, the center is left of the PC
+        let center = curve.major_points().unwrap().center;
+        assert!(center.easting < 5000.);
+        let location = curve
+            .locate_point(curve.point_at(10200., -5.).unwrap())
+            .unwrap();
+        assert!(format!("{}", location).ends_with("5.00 LT"));
+    }
+
+    #[test]
+    fn curve_direction() {
+        let horizontal_data = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_station: "100+00".to_string(),
+            input_radius: "1000".to_string(),
+            input_curve_angle: "25d10'05\" LT".to_string(),
+            input_pi_point: "5000, 5000".to_string(),
+            input_back_bearing: "0".to_string(),
+            ..Default::default()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        assert_eq!(curve.dimensions.direction, CurveDirection::Left);
+        assert!(curve.major_points().unwrap().center.easting < 5000.);
+        assert!(curve.dimensions.to_string().contains("Direction: LT"));
+
+        // toggle applies to unsigned angles, signs and bearings override it
+        let data = HorizontalData {
+            input_curve_angle: "25.168".to_string(),
+            input_direction: CurveDirection::Left,
+            ..horizontal_data.clone()
+        };
+        assert_eq!(
+            data.to_horizontal_curve().unwrap().dimensions.direction,
+            CurveDirection::Left
+        );
+        let data = HorizontalData {
+            input_curve_angle: "-25.168".to_string(),
+            input_ahead_bearing: "25.168".to_string(),
+            ..horizontal_data.clone()
+        };
+        assert!(data.to_horizontal_curve().is_err());
+
+        // past 180 degrees the curve loops, at 180 the tangents are parallel
+        let data = HorizontalData {
+            input_curve_angle: "200d RT".to_string(),
+            ..horizontal_data.clone()
+        };
+        let curve = data.to_horizontal_curve().unwrap();
+        assert!(curve.dimensions.tangent < 0.0);
+        let pt = curve.point_at(curve.stations.pt.value, 0.0).unwrap();
+        let back = curve.locate_point(pt).unwrap();
+        assert!((back.station.value - curve.stations.pt.value).abs() < 1e-6);
+        let data = HorizontalData {
+            input_curve_angle: "180".to_string(),
+            ..horizontal_data.clone()
+        };
+        assert!(data.to_horizontal_curve().is_err());
+        let data = HorizontalData {
+            input_build_method: super::HorizontalBuildDefinition::TangentCurveAngle,
+            input_tangent: "100".to_string(),
+            input_curve_angle: "200".to_string(),
+            ..horizontal_data
+        };
+        assert!(data.to_horizontal_curve().is_err());
+    }
+
+    #[test]
+    fn degree_definition() {
+        let arc = HorizontalData {
+            input_station_method: super::HorizontalStationDefinition::PC,
+            input_build_method: super::HorizontalBuildDefinition::DegreeCurveAngle,
+            input_station: "100+00".to_string(),
+            input_degree: "5d".to_string(),
+            input_curve_angle: "20d".to_string(),
+            ..Default::default()
+        };
+        let curve = arc.to_horizontal_curve().unwrap();
+        assert!((curve.dimensions.radius - 1145.92).abs() < 0.01);
+        assert!((curve.dimensions.curve_length - 400.).abs() < 1e-9);
+
+        let chord = HorizontalData {
+            input_degree_definition: super::DegreeDefinition::Chord,
+            ..arc
+        };
+        let curve = chord.to_horizontal_curve().unwrap();
+        assert!((curve.dimensions.radius - 1146.28).abs() < 0.01);
+        assert!((curve.dimensions.curve_length - 400.).abs() < 1e-9);
+
+        // full stations in chord definition are 100 long chords, each deflecting D/2
+        let interval = curve.interval_stations(100.);
+        assert!((interval.interval[1].chord.unwrap() - 100.).abs() < 1e-9);
+        assert!(
+            (interval.interval[1]
+                .deflection
+                .unwrap()
+                .to_decimal_degrees()
+                - 2.5)
+                .abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn offset_and_widening() {
+        let horizontal_data = HorizontalData {
+            input_station: "100+00".to_string(),
+            input_radius: "1000".to_string(),
+            input_curve_angle: "40".to_string(),
+            input_design_speed: "50".to_string(),
+            ..Default::default()
+        };
+        let curve = horizontal_data.to_horizontal_curve().unwrap();
+        // curves turn right by default, so right offsets are inside
+        let inside = curve.offset_curve(12.).unwrap();
+        assert_eq!(inside.radius, 988.);
+        assert!(inside.inside);
+        assert!(inside.curve_length < curve.dimensions.curve_length);
+        assert!(curve.offset_curve(1000.).is_err());
+        assert!(curve.offset_curve(-1000.).is_ok());
+
+        let cl = curve.interval_stations(50.);
+        let outside = curve.offset_curve(-12.).unwrap();
+        let edge = curve.radial_interval_stations(&outside, 50.);
+        assert_eq!(cl.interval.len(), edge.interval.len());
+        assert_eq!(cl.interval[1].deflection, edge.interval[1].deflection);
+        assert!(
+            (edge.interval[1].chord.unwrap() / cl.interval[1].chord.unwrap() - 1.012).abs() < 1e-9
+        );
+
+        let left = HorizontalData {
+            input_direction: CurveDirection::Left,
+            ..horizontal_data
+        }
+        .to_horizontal_curve()
+        .unwrap();
+        assert_eq!(left.offset_curve(12.).unwrap().radius, 1012.);
+        assert!(left.offset_curve(-1000.).is_err());
+
+        let widening = curve
+            .widening(super::widening::DesignVehicle::WB62, 2, 24.)
+            .unwrap();
+        assert!(widening.widening > 0. && widening.widening < 2.);
+        assert_eq!(widening.transition_length, 100.);
+        assert!(format!("{}", widening).contains("Full Width End"));
+
+        // a WB-62 can't turn on a 40 ft radius
+        let tight = HorizontalData {
+            input_station: "100+00".to_string(),
+            input_radius: "40".to_string(),
+            input_curve_angle: "40".to_string(),
+            ..Default::default()
+        }
+        .to_horizontal_curve()
+        .unwrap();
+        assert!(tight
+            .widening(super::widening::DesignVehicle::WB62, 2, 24.)
+            .is_err());
+        assert!(tight
+            .widening(super::widening::DesignVehicle::P, 2, 24.)
+            .is_ok());
+    }
 
     #[test]
     fn h1() {
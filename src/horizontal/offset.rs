@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::format::fmt_length;
+use crate::horizontal::calculate::Error;
+use crate::horizontal::*;
+
+/// Curve parallel to the centerline, positive offsets are right of the centerline and negative offsets left.
+#[derive(Debug, Clone, Copy)]
+pub struct OffsetCurve {
+    pub offset: f64,
+    pub inside: bool,
+    pub radius: f64,
+    pub curve_length: f64,
+    pub tangent: f64,
+    pub long_chord: f64,
+}
+
+impl fmt::Display for OffsetCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let side = match self.offset < 0.0 {
+            true => "LT",
+            false => "RT",
+        };
+        let inside = match self.inside {
+            true => "inside",
+            false => "outside",
+        };
+        writeln!(
+            f,
+            "Offset: {} {} ({})",
+            fmt_length(self.offset.abs()),
+            side,
+            inside
+        )?;
+        writeln!(f, "Radius: {}", fmt_length(self.radius))?;
+        writeln!(f, "Curve Length: {}", fmt_length(self.curve_length))?;
+        writeln!(f, "Tangent: {}", fmt_length(self.tangent))?;
+        writeln!(f, "Long Chord: {}", fmt_length(self.long_chord))?;
+        Ok(())
+    }
+}
+
+impl HorizontalCurve {
+    /// Parallel curve at a signed offset, right positive. Offsets to the side the curve turns are inside.
+    pub fn offset_curve(&self, offset: f64) -> Result<OffsetCurve, Error> {
+        let inside_offset = offset * self.dimensions.direction.turn();
+        let radius = self.dimensions.radius - inside_offset;
+        if radius <= 0.0 {
+            return Err(Error::OffsetRadius);
+        }
+        let half_angle = self.dimensions.curve_angle.radians / 2.0;
+
+        Ok(OffsetCurve {
+            offset,
+            inside: inside_offset > 0.0,
+            radius,
+            curve_length: radius * self.dimensions.curve_angle.radians,
+            tangent: radius * half_angle.tan(),
+            long_chord: 2.0 * radius * half_angle.sin(),
+        })
+    }
+
+    /// Offset curve points radial to the centerline interval stations, so they keep the centerline stations. Deflections are shared with the centerline, chords are scaled to the offset radius.
+    pub fn radial_interval_stations(
+        &self,
+        offset_curve: &OffsetCurve,
+        station_interval: f64,
+    ) -> CurveDetail {
+        let scale = offset_curve.radius / self.dimensions.radius;
+        let mut curve_detail = self.interval_stations(station_interval);
+        for station in curve_detail.interval.iter_mut() {
+            station.chord = station.chord.map(|x| x * scale);
+        }
+
+        curve_detail
+    }
+}
@@ -0,0 +1,122 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::horizontal::calculate::Error;
+use crate::horizontal::coordinates::inverse;
+use crate::horizontal::*;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StakeoutMethod {
+    #[default]
+    Deflection, // deflection angle and chord from the PC
+    TangentOffsetPC, // distance along the back tangent and offset from the PC
+    TangentOffsetPT, // distance along the ahead tangent and offset from the PT
+    ChordOffset,     // distance along the long chord from the PC and offset to the curve
+    OccupiedPoint,   // angle turned right from a backsight and distance from any setup
+}
+
+impl StakeoutMethod {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Deflection => Self::TangentOffsetPC,
+            Self::TangentOffsetPC => Self::TangentOffsetPT,
+            Self::TangentOffsetPT => Self::ChordOffset,
+            Self::ChordOffset => Self::OccupiedPoint,
+            Self::OccupiedPoint => Self::Deflection,
+        }
+    }
+}
+
+impl fmt::Display for StakeoutMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Deflection => "Deflection Angles",
+            Self::TangentOffsetPC => "Tangent Offsets from PC",
+            Self::TangentOffsetPT => "Tangent Offsets from PT",
+            Self::ChordOffset => "Long Chord Offsets",
+            Self::OccupiedPoint => "Occupied Point",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SetupDefinition {
+    #[default]
+    Coordinates,
+    StationOffset,
+}
+
+impl SetupDefinition {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Coordinates => Self::StationOffset,
+            Self::StationOffset => Self::Coordinates,
+        }
+    }
+}
+
+impl HorizontalCurve {
+    /// Interval stations staked out by the given method. Offsets are measured towards the center for tangent offsets, and away from it for chord offsets.
+    /// Occupied point stakeout needs a setup, see `setup_stations`, and returns the deflections here.
+    pub fn stakeout_stations(&self, method: StakeoutMethod, station_interval: f64) -> CurveDetail {
+        let mut curve_detail = self.interval_stations(station_interval);
+        if matches!(
+            method,
+            StakeoutMethod::Deflection | StakeoutMethod::OccupiedPoint
+        ) {
+            return curve_detail;
+        }
+
+        let radius = self.dimensions.radius;
+        let half_angle = self.dimensions.curve_angle.radians / 2.0;
+        for station in curve_detail.interval.iter_mut() {
+            let from_pc = (station.value - self.stations.pc.value) / self.dimensions.curve_length
+                * self.dimensions.curve_angle.radians;
+            let central = match method {
+                StakeoutMethod::TangentOffsetPT => 2.0 * half_angle - from_pc,
+                _ => from_pc,
+            };
+            let (along, offset) = match method {
+                StakeoutMethod::ChordOffset => (
+                    radius * (half_angle.sin() - (half_angle - central).sin()),
+                    radius * ((half_angle - central).cos() - half_angle.cos()),
+                ),
+                _ => (radius * central.sin(), radius * (1.0 - central.cos())),
+            };
+            station.deflection = None;
+            station.chord = None;
+            station.distance = Some(along);
+            station.offset = Some(offset);
+        }
+
+        curve_detail
+    }
+}
+
+impl HorizontalCurve {
+    /// Interval stations staked out from an occupied point. Deflections hold the angle turned right from the backsight, distances the horizontal distance.
+    pub fn setup_stations(
+        &self,
+        occupied: Point,
+        backsight: Point,
+        station_interval: f64,
+    ) -> Result<CurveDetail, Error> {
+        let (backsight_azimuth, backsight_distance) = inverse(occupied, backsight);
+        if backsight_distance < 1e-6 {
+            return Err(Error::CoincidentSetup);
+        }
+
+        let mut curve_detail = self.interval_stations(station_interval);
+        for station in curve_detail.interval.iter_mut() {
+            let (azimuth, distance) = inverse(occupied, self.point_at(station.value, 0.0)?);
+            station.deflection = Some(Angle {
+                radians: (azimuth.radians - backsight_azimuth.radians).rem_euclid(2.0 * PI),
+            });
+            station.chord = None;
+            station.distance = Some(distance);
+        }
+
+        Ok(curve_detail)
+    }
+}
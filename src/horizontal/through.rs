@@ -0,0 +1,106 @@
+use std::f64::consts::PI;
+
+use crate::datatypes::{Angle, Point};
+use crate::horizontal::calculate::Error;
+use crate::horizontal::coordinates::unit;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PointDefinition {
+    #[default]
+    Coordinates,
+    DistanceOffset, // back along the back tangent from the PI, offset towards the curve
+}
+
+impl PointDefinition {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Coordinates => Self::DistanceOffset,
+            Self::DistanceOffset => Self::Coordinates,
+        }
+    }
+}
+
+/// Deflection from the back tangent to the ahead tangent in radians, positive to the right.
+pub fn calc_deflection(back: Angle, ahead: Angle) -> f64 {
+    let deflection = (ahead.radians - back.radians).rem_euclid(2.0 * PI);
+    match deflection > PI {
+        true => deflection - 2.0 * PI,
+        false => deflection,
+    }
+}
+
+/// Point located by a distance back along the back tangent from the PI and an offset towards the inside of the curve.
+pub fn point_from_pi(pi: Point, back: Angle, ahead: Angle, distance: f64, offset: f64) -> Point {
+    let (n, e) = unit(back);
+    let side = calc_deflection(back, ahead).signum();
+    Point {
+        northing: pi.northing - distance * n - offset * side * e,
+        easting: pi.easting - distance * e + offset * side * n,
+    }
+}
+
+/// Solves the radius and curve angle of the curve tangent to both bearings at the PI that passes through the point.
+/// When two curves pass through the point within their arcs, the one with the smaller radius is returned.
+pub fn solve_through_point(
+    pi: Point,
+    back: Angle,
+    ahead: Angle,
+    point: Point,
+) -> Result<(f64, Angle), Error> {
+    let deflection = calc_deflection(back, ahead);
+    let half_angle = deflection.abs() / 2.0;
+    if half_angle < 1e-9 || PI / 2.0 - half_angle < 1e-9 {
+        return Err(Error::TangentBearings);
+    }
+
+    let (n1, e1) = unit(back);
+    let (n2, e2) = unit(ahead);
+    // bisector towards the center, its length from the PI is R * sec(half angle)
+    let (bn, be) = (n2 - n1, e2 - e1);
+    let b_len = bn.hypot(be);
+    let (un, ue) = (bn / b_len, be / b_len);
+
+    let (dn, de) = (point.northing - pi.northing, point.easting - pi.easting);
+    let sec = 1.0 / half_angle.cos();
+    let tan2 = half_angle.tan().powi(2);
+    let along = dn * un + de * ue;
+    let discriminant = (sec * along).powi(2) - tan2 * (dn * dn + de * de);
+    // points on a tangent touch a single curve, allow for round off
+    if discriminant < -1e-9 * (sec * along).powi(2) {
+        return Err(Error::UnreachablePoint);
+    }
+    let discriminant = discriminant.max(0.0);
+
+    let mut roots = [
+        (sec * along - discriminant.sqrt()) / tan2,
+        (sec * along + discriminant.sqrt()) / tan2,
+    ];
+    roots.sort_by(f64::total_cmp);
+
+    roots
+        .into_iter()
+        .find(|radius| {
+            if *radius <= 0.0 {
+                return false;
+            }
+            // the point must lie between the PC and PT, seen from the center
+            let tangent = radius * half_angle.tan();
+            let (cn, ce) = (radius * sec * un, radius * sec * ue);
+            let pc = (-tangent * n1 - cn, -tangent * e1 - ce);
+            let pt = (tangent * n2 - cn, tangent * e2 - ce);
+            let p = (dn - cn, de - ce);
+            let between = |a: (f64, f64), b: (f64, f64)| {
+                (a.0 * b.1 - a.1 * b.0).abs().atan2(a.0 * b.0 + a.1 * b.1)
+            };
+            (between(pc, p) + between(p, pt) - 2.0 * half_angle).abs() < 1e-6
+        })
+        .map(|radius| {
+            (
+                radius,
+                Angle {
+                    radians: 2.0 * half_angle,
+                },
+            )
+        })
+        .ok_or(Error::UnreachablePoint)
+}
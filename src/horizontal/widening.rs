@@ -0,0 +1,128 @@
+use std::fmt;
+
+use crate::format::fmt_length;
+use crate::horizontal::calculate::Error;
+use crate::horizontal::*;
+
+/// AASHTO design vehicles used for traveled way widening.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DesignVehicle {
+    P,
+    SU30,
+    WB40,
+    #[default]
+    WB62,
+    WB67,
+}
+
+impl DesignVehicle {
+    pub fn next(self) -> Self {
+        match self {
+            Self::P => Self::SU30,
+            Self::SU30 => Self::WB40,
+            Self::WB40 => Self::WB62,
+            Self::WB62 => Self::WB67,
+            Self::WB67 => Self::P,
+        }
+    }
+
+    /// Track width, wheelbases and front overhang (ft).
+    pub fn dimensions(self) -> (f64, &'static [f64], f64) {
+        match self {
+            Self::P => (7.0, &[11.0], 3.0),
+            Self::SU30 => (8.0, &[20.0], 4.0),
+            Self::WB40 => (8.0, &[12.5, 25.0], 3.0),
+            Self::WB62 => (8.5, &[19.5, 41.0], 3.0),
+            Self::WB67 => (8.5, &[19.5, 45.5], 3.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Widening {
+    pub design_vehicle: DesignVehicle,
+    pub width_on_curve: f64,
+    pub width_on_tangent: f64,
+    pub widening: f64,
+    pub transition_length: f64,
+    pub transition_start: Station,
+    pub full_start: Station,
+    pub full_end: Station,
+    pub transition_end: Station,
+}
+
+impl fmt::Display for Widening {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Width: {} -> {}",
+            fmt_length(self.width_on_tangent),
+            fmt_length(self.width_on_curve)
+        )?;
+        writeln!(f, "Widening: {}", fmt_length(self.widening))?;
+        if self.widening < 2.0 {
+            writeln!(f, "Widening under 2.00 may be disregarded.")?;
+        }
+        writeln!(
+            f,
+            "Transition Length: {}",
+            fmt_length(self.transition_length)
+        )?;
+        writeln!(f, "Begin Transition > {:.2}", self.transition_start)?;
+        writeln!(f, "Full Width Start > {:.2}", self.full_start)?;
+        writeln!(f, "Full Width End > {:.2}", self.full_end)?;
+        writeln!(f, "End Transition > {:.2}", self.transition_end)?;
+        Ok(())
+    }
+}
+
+impl HorizontalCurve {
+    /// Traveled way widening, AASHTO Green Book 3.3.10. The transition is run 2/3 on tangent and 1/3 on curve.
+    pub fn widening(
+        &self,
+        design_vehicle: DesignVehicle,
+        lanes: usize,
+        width_on_tangent: f64,
+    ) -> Result<Widening, Error> {
+        let radius = self.dimensions.radius;
+        let (u, wheelbases, front_overhang) = design_vehicle.dimensions();
+        let wheelbase_squares = wheelbases.iter().map(|x| x.powi(2)).sum::<f64>();
+        if radius.powi(2) < wheelbase_squares {
+            return Err(Error::WideningRadius);
+        }
+        let lanes = lanes as f64;
+        let clearance = match width_on_tangent / lanes {
+            w if w >= 12.0 => 3.0,
+            w if w >= 11.0 => 2.5,
+            _ => 2.0,
+        };
+
+        let track = u + radius - (radius.powi(2) - wheelbase_squares).sqrt();
+        let overhang = (radius.powi(2) + front_overhang * (2.0 * wheelbases[0] + front_overhang))
+            .sqrt()
+            - radius;
+        let extra = 0.1 * self.dimensions.design_speed as f64 / radius.sqrt();
+        let width_on_curve = lanes * (track + clearance) + (lanes - 1.0) * overhang + extra;
+        let widening = (width_on_curve - width_on_tangent).max(0.0);
+        let transition_length = (50.0 * widening).max(100.0);
+
+        let at = |value: f64| {
+            self.equations.tag(Station {
+                value,
+                ..Default::default()
+            })
+        };
+
+        Ok(Widening {
+            design_vehicle,
+            width_on_curve: width_on_curve.max(width_on_tangent),
+            width_on_tangent,
+            widening,
+            transition_length,
+            transition_start: at(self.stations.pc.value - transition_length * 2.0 / 3.0),
+            full_start: at(self.stations.pc.value + transition_length / 3.0),
+            full_end: at(self.stations.pt.value - transition_length / 3.0),
+            transition_end: at(self.stations.pt.value + transition_length * 2.0 / 3.0),
+        })
+    }
+}
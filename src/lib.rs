@@ -2,6 +2,7 @@
 
 pub mod datatypes;
 pub mod export;
+pub mod format;
 pub mod frontend;
 pub mod horizontal;
 pub mod tables;
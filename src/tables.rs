@@ -1,12 +1,16 @@
 // use anyhow::{Result, anyhow};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::sync::RwLock;
 
-use crate::datatypes::{DesignStandard, SightType};
+use crate::datatypes::{DecisionManeuver, DesignStandard, SightType, SpeedLookup};
+use crate::format::fmt_length;
 
 //AASHTO GREEN BOOK TABLE 3-35
 lazy_static! {
-    static ref AASHTO_SIGHT_TABLE: HashMap<i32, (f64, f64)> = { //stopping, crest passing, sag passing
+    static ref AASHTO_SIGHT_TABLE: HashMap<i32, (f64, f64)> = { //stopping, passing
         let mut m = HashMap::new();
         m.insert(15, (80.0, 400.0));
         m.insert(20, (115.0, 400.0));
@@ -27,6 +31,26 @@ lazy_static! {
     };
 }
 
+//AASHTO GREEN BOOK TABLE 3-3
+lazy_static! {
+    static ref AASHTO_DECISION_TABLE: HashMap<i32, (f64, f64, f64, f64, f64)> = { //avoidance maneuver A, B, C, D, E
+        let mut m = HashMap::new();
+        m.insert(30, (220.0, 490.0, 450.0, 535.0, 620.0));
+        m.insert(35, (275.0, 590.0, 525.0, 625.0, 720.0));
+        m.insert(40, (330.0, 690.0, 600.0, 715.0, 825.0));
+        m.insert(45, (395.0, 800.0, 675.0, 800.0, 930.0));
+        m.insert(50, (465.0, 910.0, 750.0, 890.0, 1030.0));
+        m.insert(55, (535.0, 1030.0, 865.0, 980.0, 1135.0));
+        m.insert(60, (610.0, 1150.0, 990.0, 1125.0, 1280.0));
+        m.insert(65, (695.0, 1275.0, 1050.0, 1220.0, 1365.0));
+        m.insert(70, (780.0, 1410.0, 1105.0, 1275.0, 1445.0));
+        m.insert(75, (875.0, 1545.0, 1180.0, 1365.0, 1545.0));
+        m.insert(80, (970.0, 1685.0, 1260.0, 1470.0, 1650.0));
+
+        m
+    };
+}
+
 //CALTRANS HDM TABLE 201-1 & TABLE 201-7 (2020 7th ed.)
 lazy_static! {
     static ref HDM_SIGHT_TABLE: HashMap<i32, (f64, f64, f64)> = { //stopping, passing, decision
@@ -51,27 +75,509 @@ lazy_static! {
     };
 }
 
+//AASHTO GREEN BOOK TABLE 3-34 & TABLE 3-36
+lazy_static! {
+    static ref AASHTO_K_TABLE: HashMap<i32, (f64, f64)> = { //crest, sag
+        let mut m = HashMap::new();
+        m.insert(15, (3.0, 10.0));
+        m.insert(20, (7.0, 17.0));
+        m.insert(25, (12.0, 26.0));
+        m.insert(30, (19.0, 37.0));
+        m.insert(35, (29.0, 49.0));
+        m.insert(40, (44.0, 64.0));
+        m.insert(45, (61.0, 79.0));
+        m.insert(50, (84.0, 96.0));
+        m.insert(55, (114.0, 115.0));
+        m.insert(60, (151.0, 136.0));
+        m.insert(65, (193.0, 157.0));
+        m.insert(70, (247.0, 181.0));
+        m.insert(75, (312.0, 206.0));
+        m.insert(80, (384.0, 231.0));
+
+        m
+    };
+}
+
+//CALTRANS HDM INDEX 204.4, K = S^2/1329 (crest) & K = S^2/(400+3.5S) (sag) from TABLE 201-1
+lazy_static! {
+    static ref HDM_K_TABLE: HashMap<i32, (f64, f64)> = { //crest, sag
+        let mut m = HashMap::new();
+        m.insert(10, (2.0, 5.0));
+        m.insert(15, (8.0, 14.0));
+        m.insert(20, (12.0, 19.0));
+        m.insert(25, (17.0, 25.0));
+        m.insert(30, (31.0, 37.0));
+        m.insert(35, (48.0, 50.0));
+        m.insert(40, (68.0, 63.0));
+        m.insert(45, (98.0, 79.0));
+        m.insert(50, (140.0, 98.0));
+        m.insert(55, (189.0, 117.0));
+        m.insert(60, (254.0, 139.0));
+        m.insert(65, (328.0, 161.0));
+        m.insert(70, (424.0, 186.0));
+        m.insert(75, (531.0, 212.0));
+        m.insert(80, (651.0, 237.0));
+
+        m
+    };
+}
+
+//User-defined standards loaded at runtime, indexed by DesignStandard::Custom.
+lazy_static! {
+    static ref CUSTOM_STANDARDS: RwLock<Vec<CustomStandard>> = RwLock::new(Vec::new());
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CurveConstants {
+    pub crest: f64,
+    pub sag_base: f64,
+    pub sag_factor: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CustomStandard {
+    pub name: String,
+    pub sight: HashMap<i32, (f64, Option<f64>, Option<f64>)>, //stopping, passing, decision
+    pub k: HashMap<i32, (f64, f64)>,                          //crest, sag
+    pub crest_stopping: Option<f64>,
+    pub crest_passing: Option<f64>,
+    pub crest_decision: Option<f64>,
+    pub sag_base: f64,
+    pub sag_factor: f64,
+}
+
+impl CustomStandard {
+    /// Parses a standard from `key = value` lines, `#` starts a comment.
+    ///
+    /// ```text
+    /// name = Metro County
+    /// crest_stopping = 1329
+    /// crest_passing = 3093
+    /// sag_base = 400
+    /// sag_factor = 3.5
+    /// sight = 25, 150, 950, 450   # speed, stopping, passing, decision
+    /// k = 25, 17, 25              # speed, crest, sag
+    /// ```
+    pub fn from(raw_data: &str) -> Result<Self, Error> {
+        let mut standard = CustomStandard {
+            sag_base: 400.0,
+            sag_factor: 3.5,
+            ..Default::default()
+        };
+
+        for (i, line) in raw_data.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(Error::ParseLine { line: i + 1 })?;
+            let (key, value) = (key.trim(), value.trim());
+            // constants divide the sight distance, so they must be positive
+            let number = || match value.parse::<f64>() {
+                Ok(x) if x > 0.0 && x.is_finite() => Ok(x),
+                _ => Err(Error::ParseValue { line: i + 1 }),
+            };
+
+            match key {
+                "name" => standard.name = value.to_string(),
+                "crest_stopping" => standard.crest_stopping = Some(number()?),
+                "crest_passing" => standard.crest_passing = Some(number()?),
+                "crest_decision" => standard.crest_decision = Some(number()?),
+                "sag_base" => standard.sag_base = number()?,
+                "sag_factor" => standard.sag_factor = number()?,
+                "sight" => {
+                    let row = parse_row(value, i + 1)?;
+                    let speed = parse_speed(&row, i + 1)?;
+                    if row.len() < 2 || row.len() > 4 || standard.sight.contains_key(&speed) {
+                        return Err(Error::ParseValue { line: i + 1 });
+                    }
+                    standard
+                        .sight
+                        .insert(speed, (row[1], row.get(2).copied(), row.get(3).copied()));
+                }
+                "k" => {
+                    let row = parse_row(value, i + 1)?;
+                    let speed = parse_speed(&row, i + 1)?;
+                    if row.len() != 3 || standard.k.contains_key(&speed) {
+                        return Err(Error::ParseValue { line: i + 1 });
+                    }
+                    standard.k.insert(speed, (row[1], row[2]));
+                }
+                _ => return Err(Error::UnknownKey { line: i + 1 }),
+            }
+        }
+
+        if standard.name.is_empty() {
+            return Err(Error::NoName);
+        }
+        if standard.sight.is_empty() {
+            return Err(Error::NoRows);
+        }
+
+        Ok(standard)
+    }
+}
+
+fn parse_row(value: &str, line: usize) -> Result<Vec<f64>, Error> {
+    value
+        .split(',')
+        .map(|x| x.trim().parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()
+        .map_err(|x| Error::ParseValue { line })
+}
+
+/// Leading speed of a row, which must be a whole number.
+fn parse_speed(row: &[f64], line: usize) -> Result<i32, Error> {
+    match row.first() {
+        Some(x) if x.fract() == 0.0 && x.abs() <= i32::MAX as f64 => Ok(*x as i32),
+        _ => Err(Error::ParseValue { line }),
+    }
+}
+
+/// Reads a standard from file and registers it, replacing any standard of the same name.
+pub fn load_standard(path: &str) -> anyhow::Result<DesignStandard> {
+    let standard = CustomStandard::from(&fs::read_to_string(path)?)?;
+    let mut standards = CUSTOM_STANDARDS
+        .write()
+        .map_err(|x| anyhow::anyhow!("Standard registry is poisoned."))?;
+
+    match standards.iter().position(|x| x.name == standard.name) {
+        Some(i) => {
+            standards[i] = standard;
+            Ok(DesignStandard::Custom(i))
+        }
+        None => {
+            standards.push(standard);
+            Ok(DesignStandard::Custom(standards.len() - 1))
+        }
+    }
+}
+
+pub fn custom_standard_count() -> usize {
+    CUSTOM_STANDARDS.read().map(|x| x.len()).unwrap_or_default()
+}
+
+pub fn custom_standard_name(index: usize) -> Option<String> {
+    CUSTOM_STANDARDS
+        .read()
+        .ok()?
+        .get(index)
+        .map(|x| x.name.clone())
+}
+
+pub fn get_curve_constants(
+    design_standard: DesignStandard,
+    sight_type: SightType,
+) -> Option<CurveConstants> {
+    let crest = match (design_standard, sight_type) {
+        (DesignStandard::AASHTO, SightType::Stopping) => 2158.0,
+        (DesignStandard::AASHTO, SightType::Passing) => 2800.0,
+        (DesignStandard::AASHTO, SightType::Decision(_)) => 2158.0,
+        (DesignStandard::CALTRANS, SightType::Stopping) => 1329.0,
+        (DesignStandard::CALTRANS, SightType::Passing) => 3093.0,
+        (DesignStandard::CALTRANS, SightType::Decision(_)) => 1329.0,
+        (DesignStandard::Custom(i), _) => {
+            let standards = CUSTOM_STANDARDS.read().ok()?;
+            let standard = standards.get(i)?;
+            let crest = match sight_type {
+                SightType::Stopping => standard.crest_stopping,
+                SightType::Passing => standard.crest_passing,
+                SightType::Decision(_) => standard.crest_decision,
+            }?;
+            return Some(CurveConstants {
+                crest,
+                sag_base: standard.sag_base,
+                sag_factor: standard.sag_factor,
+            });
+        }
+    };
+
+    Some(CurveConstants {
+        crest,
+        sag_base: 400.0,
+        sag_factor: 3.5,
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TableLookup {
+    pub value: f64,
+    pub design_speed: f64,
+    pub method: SpeedLookup,
+}
+
+impl fmt::Display for TableLookup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Table Value: {} @ {:.1} ({:?})",
+            fmt_length(self.value),
+            self.design_speed,
+            self.method
+        )
+    }
+}
+
+fn lookup<T>(
+    table: &HashMap<i32, T>,
+    design_speed: i32,
+    method: SpeedLookup,
+    column: impl Fn(&T) -> Option<f64>,
+) -> Option<TableLookup> {
+    if let Some(row) = table.get(&design_speed) {
+        return Some(TableLookup {
+            value: column(row)?,
+            design_speed: design_speed as f64,
+            method: SpeedLookup::Exact,
+        });
+    }
+
+    let upper = table.keys().filter(|k| **k > design_speed).min()?;
+    match method {
+        SpeedLookup::Exact => None,
+        SpeedLookup::RoundUp => Some(TableLookup {
+            value: column(&table[upper])?,
+            design_speed: *upper as f64,
+            method,
+        }),
+        SpeedLookup::Interpolate => {
+            let lower = table.keys().filter(|k| **k < design_speed).max()?;
+            let (y0, y1) = (column(&table[lower])?, column(&table[upper])?);
+            let t = (design_speed - lower) as f64 / (upper - lower) as f64;
+            Some(TableLookup {
+                value: y0 + (y1 - y0) * t,
+                design_speed: design_speed as f64,
+                method,
+            })
+        }
+    }
+}
+
 pub fn get_min_sight(
     design_speed: i32,
     design_standard: DesignStandard,
     sight_type: SightType,
-) -> Option<f64> {
+    method: SpeedLookup,
+) -> Option<TableLookup> {
     match design_standard {
-        DesignStandard::AASHTO => {
-            let row = AASHTO_SIGHT_TABLE.get(&design_speed)?;
-            match sight_type {
-                SightType::Stopping => Some(row.0),
-                SightType::Passing => Some(row.1),
-                SightType::Decision => None,
-            }
-        }
+        DesignStandard::AASHTO => match sight_type {
+            SightType::Decision(maneuver) => lookup(
+                &AASHTO_DECISION_TABLE,
+                design_speed,
+                method,
+                |row| match maneuver {
+                    DecisionManeuver::A => Some(row.0),
+                    DecisionManeuver::B => Some(row.1),
+                    DecisionManeuver::C => Some(row.2),
+                    DecisionManeuver::D => Some(row.3),
+                    DecisionManeuver::E => Some(row.4),
+                },
+            ),
+            _ => lookup(
+                &AASHTO_SIGHT_TABLE,
+                design_speed,
+                method,
+                |row| match sight_type {
+                    SightType::Passing => Some(row.1),
+                    _ => Some(row.0),
+                },
+            ),
+        },
         DesignStandard::CALTRANS => {
-            let row = HDM_SIGHT_TABLE.get(&design_speed)?;
-            match sight_type {
-                SightType::Stopping => Some(row.0),
-                SightType::Passing => Some(row.1),
-                SightType::Decision => Some(row.2),
+            lookup(
+                &HDM_SIGHT_TABLE,
+                design_speed,
+                method,
+                |row| match sight_type {
+                    SightType::Stopping => Some(row.0),
+                    SightType::Passing => Some(row.1),
+                    SightType::Decision(_) => Some(row.2),
+                },
+            )
+        }
+        DesignStandard::Custom(i) => {
+            let standards = CUSTOM_STANDARDS.read().ok()?;
+            lookup(
+                &standards.get(i)?.sight,
+                design_speed,
+                method,
+                |row| match sight_type {
+                    SightType::Stopping => Some(row.0),
+                    SightType::Passing => row.1,
+                    SightType::Decision(_) => row.2,
+                },
+            )
+        }
+    }
+}
+
+pub fn get_min_k(
+    design_speed: i32,
+    design_standard: DesignStandard,
+    sag: bool,
+    method: SpeedLookup,
+) -> Option<TableLookup> {
+    let column = |row: &(f64, f64)| match sag {
+        false => Some(row.0),
+        true => Some(row.1),
+    };
+    match design_standard {
+        DesignStandard::AASHTO => lookup(&AASHTO_K_TABLE, design_speed, method, column),
+        DesignStandard::CALTRANS => lookup(&HDM_K_TABLE, design_speed, method, column),
+        DesignStandard::Custom(i) => {
+            let standards = CUSTOM_STANDARDS.read().ok()?;
+            lookup(&standards.get(i)?.k, design_speed, method, column)
+        }
+    }
+}
+
+/// Design Standard File Errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Line is missing an "=" sign.
+    #[error("Line {line} is missing an \"=\" sign.")]
+    ParseLine { line: usize },
+    /// Line has a misconfigured value.
+    #[error("Line {line} is misconfigured with unexpected symbol.")]
+    ParseValue { line: usize },
+    /// Line has an unknown key.
+    #[error("Line {line} has an unknown key.")]
+    UnknownKey { line: usize },
+    /// Standard requires a name.
+    #[error("Standard requires a name.")]
+    NoName,
+    /// Standard requires at least one sight row.
+    #[error("Standard requires at least one sight row.")]
+    NoRows,
+}
+
+#[cfg(test)]
+mod table_tests {
+    use super::{get_curve_constants, get_min_sight, CustomStandard, Error};
+    use crate::datatypes::{DecisionManeuver, DesignStandard, SightType, SpeedLookup};
+
+    #[test]
+    fn custom_standard() {
+        let standard = CustomStandard::from(
+            "name = Metro County # comment\n\
+             crest_stopping = 1329\n\
+             sight = 25, 150, 950\n\
+             sight = 30, 200\n\
+             k = 25, 17, 25",
+        )
+        .unwrap();
+        assert_eq!(standard.name, "Metro County");
+        assert_eq!(standard.sight[&25], (150.0, Some(950.0), None));
+        assert_eq!(standard.sight[&30], (200.0, None, None));
+        assert_eq!(standard.k[&25], (17.0, 25.0));
+        assert_eq!(standard.sag_base, 400.0);
+
+        assert!(CustomStandard::from("sight = 25, 150").is_err());
+        assert!(CustomStandard::from("name = A\nsight = 25, x").is_err());
+        assert!(CustomStandard::from("name = A\nspeed = 25").is_err());
+        for constant in ["crest_stopping = 0", "sag_base = -400", "sag_factor = inf"] {
+            assert!(matches!(
+                CustomStandard::from(&format!("name = A\nsight = 25, 150\n{}", constant)),
+                Err(Error::ParseValue { line: 3 })
+            ));
+        }
+        assert!(matches!(
+            CustomStandard::from("name = A\nsight = 25.5, 150"),
+            Err(Error::ParseValue { line: 2 })
+        ));
+        assert!(matches!(
+            CustomStandard::from("name = A\nsight = 25, 150\nk = 25, 17, 25\nk = 25, 18, 26"),
+            Err(Error::ParseValue { line: 4 })
+        ));
+    }
+
+    #[test]
+    fn exact_lookup() {
+        let w = get_min_sight(
+            60,
+            DesignStandard::AASHTO,
+            SightType::Stopping,
+            SpeedLookup::Exact,
+        );
+        assert_eq!(w.unwrap().value, 570.0);
+        let w = get_min_sight(
+            62,
+            DesignStandard::AASHTO,
+            SightType::Stopping,
+            SpeedLookup::Exact,
+        );
+        assert!(w.is_none());
+    }
+
+    #[test]
+    fn decision_lookup() {
+        let w = get_min_sight(
+            60,
+            DesignStandard::AASHTO,
+            SightType::Decision(DecisionManeuver::C),
+            SpeedLookup::Exact,
+        );
+        assert_eq!(w.unwrap().value, 990.0);
+        let w = get_min_sight(
+            60,
+            DesignStandard::CALTRANS,
+            SightType::Decision(DecisionManeuver::C),
+            SpeedLookup::Exact,
+        );
+        assert_eq!(w.unwrap().value, 990.0);
+    }
+
+    #[test]
+    fn every_combination_has_constants() {
+        for design_standard in [DesignStandard::AASHTO, DesignStandard::CALTRANS] {
+            for sight_type in [
+                SightType::Stopping,
+                SightType::Passing,
+                SightType::Decision(DecisionManeuver::A),
+            ] {
+                assert!(get_curve_constants(design_standard, sight_type).is_some());
             }
         }
     }
+
+    #[test]
+    fn round_up_lookup() {
+        let w = get_min_sight(
+            62,
+            DesignStandard::AASHTO,
+            SightType::Stopping,
+            SpeedLookup::RoundUp,
+        )
+        .unwrap();
+        assert_eq!(w.value, 645.0);
+        assert_eq!(w.design_speed, 65.0);
+        assert!(get_min_sight(
+            85,
+            DesignStandard::AASHTO,
+            SightType::Stopping,
+            SpeedLookup::RoundUp
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn interpolated_lookup() {
+        let w = get_min_sight(
+            62,
+            DesignStandard::AASHTO,
+            SightType::Stopping,
+            SpeedLookup::Interpolate,
+        )
+        .unwrap();
+        assert!((w.value - 600.0).abs() < 1e-9);
+        assert!(get_min_sight(
+            12,
+            DesignStandard::AASHTO,
+            SightType::Stopping,
+            SpeedLookup::Interpolate
+        )
+        .is_none());
+    }
 }
@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Result};
 
-use crate::tables::get_min_sight;
+use crate::tables::{get_curve_constants, get_min_k, get_min_sight, TableLookup};
 use crate::vertical::*;
 
 pub type ObstacleReturn = Result<(bool, ObstacleStation, Station, f64), Error>;
@@ -17,15 +17,17 @@ pub struct VerticalDimensions {
     pub incoming_grade: f64,
     pub outgoing_grade: f64,
     pub curve_length: f64,
+    pub k_value: Option<f64>, // None when the grades are equal
     pub external: f64,
     pub design_speed: i32,
     pub sustained_downgrade: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct VerticalCurve {
     pub dimensions: VerticalDimensions,
     pub stations: VerticalStations,
+    pub equations: StationEquations,
 }
 
 impl VerticalCurve {
@@ -43,7 +45,7 @@ impl VerticalCurve {
             // --
             return Ok(0.0);
         }
-        let mut min_sight_adjusted = min_sight.clone();
+        let mut min_sight_adjusted = min_sight;
         if a >= 2. && design_speed >= 40 && min_sight_adjusted < 10. * design_speed as f64 {
             min_sight_adjusted = 10. * design_speed as f64;
             println!("large min used.");
@@ -52,163 +54,43 @@ impl VerticalCurve {
             min_sight_adjusted = 200.;
             println!("small min used.");
         }
-        match design_standard {
-            DesignStandard::AASHTO => {
-                match sight_type {
-                    SightType::Stopping => {
-                        if grade_break.is_sign_positive() {
-                            // \/
-                            let l =
-                                a * min_sight_adjusted.powi(2) / (400.0 + 3.5 * min_sight_adjusted);
-                            if l >= min_sight_adjusted {
-                                return Ok(l);
-                            }
-                            let l =
-                                2.0 * min_sight_adjusted - (400.0 + 3.5 * min_sight_adjusted) / a;
-                            if min_sight_adjusted > l {
-                                return Ok(l);
-                            }
-
-                            Err(anyhow!(format!(
-                                "Failed at: {:?} - {:?} - {}",
-                                design_standard, sight_type, "SAG"
-                            )))
-                        } else {
-                            // /\
-                            let l = a * min_sight_adjusted.powi(2) / 2158.0;
-                            if l >= min_sight_adjusted {
-                                return Ok(l);
-                            }
-                            let l = 2.0 * min_sight_adjusted - 2158.0 / a;
-                            if min_sight_adjusted > l {
-                                return Ok(l);
-                            }
-
-                            Err(anyhow!(format!(
-                                "Failed at: {:?} - {:?} - {}",
-                                design_standard, sight_type, "CREST"
-                            )))
-                        }
-                    }
-                    SightType::Passing => {
-                        if grade_break.is_sign_positive() {
-                            // \/
-                            let l =
-                                a * min_sight_adjusted.powi(2) / (400.0 + 3.5 * min_sight_adjusted);
-                            if l >= min_sight_adjusted {
-                                return Ok(l);
-                            }
-                            let l =
-                                2.0 * min_sight_adjusted - (400.0 + 3.5 * min_sight_adjusted) / a;
-                            if min_sight_adjusted > l {
-                                return Ok(l);
-                            }
-
-                            Err(anyhow!(format!(
-                                "Failed at: {:?} - {:?} - {}",
-                                design_standard, sight_type, "SAG"
-                            )))
-                        } else {
-                            // /\
-                            let l = a * min_sight_adjusted.powi(2) / 2800.0;
-                            if l >= min_sight_adjusted {
-                                return Ok(l);
-                            }
-                            let l = 2.0 * min_sight_adjusted - 2800.0 / a;
-                            if min_sight_adjusted > l {
-                                return Ok(l);
-                            }
-
-                            Err(anyhow!(format!(
-                                "Failed at: {:?} - {:?} - {}",
-                                design_standard, sight_type, "CREST"
-                            )))
-                        }
-                    }
-                    _ => Err(anyhow!(format!(
-                        "{:?} - {:?} hasn't been implimented.",
-                        design_standard, sight_type
-                    ))),
-                }
+        let constants =
+            get_curve_constants(design_standard, sight_type).ok_or(anyhow!(format!(
+                "{} - {:?} isn't defined by the standard.",
+                design_standard, sight_type
+            )))?;
+
+        if grade_break.is_sign_positive() {
+            // \/
+            let denominator = constants.sag_base + constants.sag_factor * min_sight_adjusted;
+            let l = a * min_sight_adjusted.powi(2) / denominator;
+            if l >= min_sight_adjusted {
+                return Ok(l);
+            }
+            let l = 2.0 * min_sight_adjusted - denominator / a;
+            if min_sight_adjusted > l {
+                return Ok(l);
             }
-            DesignStandard::CALTRANS => {
-                match sight_type {
-                    SightType::Stopping => {
-                        if grade_break.is_sign_positive() {
-                            // \/
-                            let l =
-                                a * min_sight_adjusted.powi(2) / (400.0 + 3.5 * min_sight_adjusted);
-                            if l >= min_sight_adjusted {
-                                return Ok(l);
-                            }
-                            let l =
-                                2.0 * min_sight_adjusted - (400.0 + 3.5 * min_sight_adjusted) / a;
-                            if min_sight_adjusted > l {
-                                return Ok(l);
-                            }
-
-                            Err(anyhow!(format!(
-                                "Failed at: {:?} - {:?} - {}",
-                                design_standard, sight_type, "SAG"
-                            )))
-                        } else {
-                            // /\
-                            let l = a * min_sight_adjusted.powi(2) / 1329.0;
-                            if l >= min_sight_adjusted {
-                                return Ok(l);
-                            }
-                            let l = 2.0 * min_sight_adjusted - 1329.0 / a;
-                            if min_sight_adjusted > l {
-                                return Ok(l);
-                            }
-
-                            Err(anyhow!(format!(
-                                "Failed at: {:?} - {:?} - {}",
-                                design_standard, sight_type, "CREST"
-                            )))
-                        }
-                    }
-                    SightType::Decision => {
-                        if grade_break.is_sign_positive() {
-                            // \/
-                            let l =
-                                a * min_sight_adjusted.powi(2) / (400.0 + 3.5 * min_sight_adjusted);
-                            if l >= min_sight_adjusted {
-                                return Ok(l);
-                            }
-                            let l =
-                                2.0 * min_sight_adjusted - (400.0 + 3.5 * min_sight_adjusted) / a;
-                            if min_sight_adjusted > l {
-                                return Ok(l);
-                            }
-
-                            Err(anyhow!(format!(
-                                "Failed at: {:?} - {:?} - {}",
-                                design_standard, sight_type, "SAG"
-                            )))
-                        } else {
-                            // /\
-                            let l = a * min_sight_adjusted.powi(2) / 1329.0;
-                            if l >= min_sight_adjusted {
-                                return Ok(l);
-                            }
-                            let l = 2.0 * min_sight_adjusted - 1329.0 / a;
-                            if min_sight_adjusted > l {
-                                return Ok(l);
-                            }
 
-                            Err(anyhow!(format!(
-                                "Failed at: {:?} - {:?} - {}",
-                                design_standard, sight_type, "CREST"
-                            )))
-                        }
-                    }
-                    _ => Err(anyhow!(format!(
-                        "{:?} - {:?} hasn't been implimented.",
-                        design_standard, sight_type
-                    ))),
-                }
+            Err(anyhow!(format!(
+                "Failed at: {} - {:?} - {}",
+                design_standard, sight_type, "SAG"
+            )))
+        } else {
+            // /\
+            let l = a * min_sight_adjusted.powi(2) / constants.crest;
+            if l >= min_sight_adjusted {
+                return Ok(l);
+            }
+            let l = 2.0 * min_sight_adjusted - constants.crest / a;
+            if min_sight_adjusted > l {
+                return Ok(l);
             }
+
+            Err(anyhow!(format!(
+                "Failed at: {} - {:?} - {}",
+                design_standard, sight_type, "CREST"
+            )))
         }
     }
 
@@ -216,23 +98,93 @@ impl VerticalCurve {
         &self,
         design_standard: DesignStandard,
         sight_type: SightType,
+        speed_lookup: SpeedLookup,
         adjustment: f64,
-    ) -> Result<(bool, f64)> {
-        let min_sight = get_min_sight(self.dimensions.design_speed, design_standard, sight_type);
+    ) -> Result<(bool, f64, TableLookup)> {
+        let min_sight = get_min_sight(
+            self.dimensions.design_speed,
+            design_standard,
+            sight_type,
+            speed_lookup,
+        );
         match min_sight {
             Some(w) => {
                 let min_curve_length =
-                    self.calc_min_curve_length(w * adjustment, design_standard, sight_type)?;
+                    self.calc_min_curve_length(w.value * adjustment, design_standard, sight_type)?;
 
                 Ok((
                     (self.dimensions.curve_length >= min_curve_length),
                     min_curve_length,
+                    w,
                 ))
             }
             None => Err(anyhow!("Design speed isn't specified in the manual.")),
         }
     }
 
+    pub fn is_k_compliant(
+        &self,
+        design_standard: DesignStandard,
+        speed_lookup: SpeedLookup,
+    ) -> Result<(bool, f64, TableLookup)> {
+        let sag =
+            (self.dimensions.outgoing_grade - self.dimensions.incoming_grade).is_sign_positive();
+        let min_k = get_min_k(
+            self.dimensions.design_speed,
+            design_standard,
+            sag,
+            speed_lookup,
+        );
+        let k_value = self
+            .dimensions
+            .k_value
+            .ok_or(anyhow!("K value doesn't apply, the grades are equal."))?;
+        match min_k {
+            Some(w) => Ok(((k_value >= w.value), w.value, w)),
+            None => Err(anyhow!("Design speed isn't specified in the manual.")),
+        }
+    }
+
+    pub fn grade_adjusted_sight(
+        &self,
+        reaction_time: f64,
+        deceleration: f64,
+    ) -> Result<(f64, f64)> {
+        let design_speed = self.dimensions.design_speed as f64;
+        let ahead = calc_stopping_sight(
+            design_speed,
+            reaction_time,
+            deceleration,
+            self.dimensions.outgoing_grade,
+        )?;
+        let back = calc_stopping_sight(
+            design_speed,
+            reaction_time,
+            deceleration,
+            -self.dimensions.incoming_grade,
+        )?;
+
+        Ok((ahead, back))
+    }
+
+    pub fn is_grade_adjusted_compliant(
+        &self,
+        design_standard: DesignStandard,
+        reaction_time: f64,
+        deceleration: f64,
+    ) -> Result<(bool, f64, f64)> {
+        let (ahead, back) = self.grade_adjusted_sight(reaction_time, deceleration)?;
+        let governing = ahead.max(back);
+        let min_curve_length =
+            self.calc_min_curve_length(governing, design_standard, SightType::Stopping)?;
+
+        Ok((
+            (self.dimensions.curve_length >= min_curve_length),
+            min_curve_length,
+            governing,
+        ))
+    }
+
     pub fn obstacle_compliant(&self, obstacle_detail: &ObstacleDetail) -> Vec<ObstacleReturn> {
         let mut obstacle_return = Vec::new();
 
@@ -277,11 +229,11 @@ impl VerticalCurve {
             let elevation = self.stations.pvc.elevation.unwrap()
                 + self.dimensions.incoming_grade * distance_delta
                 + a * distance_delta.powi(2);
-            return Ok(Station {
+            return Ok(self.equations.tag(Station {
                 value: station.value,
                 elevation: Some(elevation),
                 ..Default::default()
-            });
+            }));
         }
 
         Err(Error::ParseStation { station })
@@ -294,4 +246,7 @@ pub enum Error {
     /// Obstacle station is outside the curve.
     #[error("{station} is outside the curve.")]
     ParseStation { station: Station },
+    /// Equal grades have no grade break for K to scale.
+    #[error("K value can't set the length when the grades are equal.")]
+    EqualGradesK,
 }
@@ -1,18 +1,20 @@
 use std::fmt;
 
+use crate::format::{fmt_k, fmt_length};
 use crate::vertical::*;
 
 impl fmt::Display for VerticalDimensions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
         // writeln!(f, "~ Curve Details")?;
-        writeln!(f, "Curve Length: {:.2}", self.curve_length)?;
+        writeln!(f, "Curve Length: {}", fmt_length(self.curve_length))?;
+        writeln!(f, "K: {}", fmt_k(self.k_value))?;
         writeln!(
             f,
             "Grade: {:.2}% -> {:.2}%",
             self.incoming_grade * 100.0,
             self.outgoing_grade * 100.0
         )?;
-        writeln!(f, "External: {:.2}", self.external.abs())?;
+        writeln!(f, "External: {}", fmt_length(self.external.abs()))?;
         Ok(())
     }
 }
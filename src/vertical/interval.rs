@@ -11,44 +11,34 @@ impl VerticalCurve {
             + self.dimensions.incoming_grade * x
             + a * x.powi(2);
 
-        Station {
+        self.equations.tag(Station {
             value,
             elevation: Some(elevation),
             ..Default::default()
-        }
+        })
     }
 
     pub fn interval_stations(&self, station_interval: f64) -> CurveDetail {
-        let mut curve_detail = CurveDetail { interval: vec![] };
-        let pvc_elevation = self.stations.pvc.elevation;
+        let pvc = self.stations.pvc.value;
+        let pvc_elevation = self.stations.pvc.elevation.unwrap();
         let a = (self.dimensions.outgoing_grade - self.dimensions.incoming_grade)
             / (2.0 * self.dimensions.curve_length);
-        let first_station = ((self.stations.pvc.value * (100.0 / station_interval) / 100.0).ceil())
-            / (100.0 / station_interval)
-            * 100.0;
-        let mut count = 0usize;
-        let mut x = first_station - self.stations.pvc.value;
-        let mut running = Station {
-            value: first_station,
-            elevation: Some(
-                pvc_elevation.unwrap() + self.dimensions.incoming_grade * x + a * x.powi(2),
-            ),
-            ..Default::default()
-        };
-
-        loop {
-            curve_detail.interval.push(running);
-
-            running.value += station_interval;
-            x = running.value - first_station;
-            running.elevation =
-                Some(pvc_elevation.unwrap() + self.dimensions.incoming_grade * x + a * x.powi(2));
-            count += 1;
-            if running.value >= self.stations.pvt.value || count >= 100 {
-                break;
-            }
-        }
+        let interval = self
+            .equations
+            .interval_values(pvc, self.stations.pvt.value, station_interval)
+            .into_iter()
+            .map(|value| {
+                let x = value - pvc;
+                self.equations.tag(Station {
+                    value,
+                    elevation: Some(
+                        pvc_elevation + self.dimensions.incoming_grade * x + a * x.powi(2),
+                    ),
+                    ..Default::default()
+                })
+            })
+            .collect();
 
-        curve_detail
+        CurveDetail { interval }
     }
 }
@@ -8,7 +8,7 @@ pub mod interval;
 
 use self::calculate::*;
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum VerticalDefinition {
     #[default]
     PVI,
@@ -26,7 +26,23 @@ impl VerticalDefinition {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum VerticalLengthDefinition {
+    #[default]
+    Length,
+    K,
+}
+
+impl VerticalLengthDefinition {
+    pub fn next(self) -> Self {
+        match self {
+            VerticalLengthDefinition::Length => VerticalLengthDefinition::K,
+            VerticalLengthDefinition::K => VerticalLengthDefinition::Length,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum ObstacleType {
     #[default]
     Above, //like a bridge
@@ -42,7 +58,7 @@ impl ObstacleType {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct VerticalData {
     pub input_directory: String,
     pub success_flags: [ExportSuccess; 2],
@@ -51,23 +67,40 @@ pub struct VerticalData {
     pub input_elevation: String,
     pub input_incoming_grade: String,
     pub input_outgoing_grade: String,
+    pub input_length_method: VerticalLengthDefinition,
     pub input_length: String,
+    pub input_k_value: String,
     pub input_station_interval: String,
+    pub input_station_equations: String,
     pub input_sight_type: SightType,
     pub input_design_speed: String,
+    pub input_speed_lookup: SpeedLookup,
     pub input_design_standard: DesignStandard,
+    pub standard_error: String,
     pub sustained_downgrade: bool,
+    pub input_reaction_time: String,
+    pub input_deceleration: String,
     pub input_obstacle_station: String,
     pub input_obstacle_elevation: String,
     pub input_obstacle_type: ObstacleType,
     pub obstacles: ObstacleDetail,
+    pub selected_obstacle: Option<usize>,
+    pub list_error: String,
 }
 
 impl VerticalData {
     fn to_dimensions(&self) -> Result<VerticalDimensions> {
         let incoming_grade = coerce_grade(&self.input_incoming_grade)?;
         let outgoing_grade = coerce_grade(&self.input_outgoing_grade)?;
-        let curve_length = coerce_length(&self.input_length)?;
+        let grade_break = (outgoing_grade - incoming_grade).abs() * 100.0;
+        let curve_length = match self.input_length_method {
+            VerticalLengthDefinition::Length => coerce_length(&self.input_length)?,
+            VerticalLengthDefinition::K if grade_break == 0.0 => {
+                Err(calculate::Error::EqualGradesK)?
+            }
+            VerticalLengthDefinition::K => coerce_length(&self.input_k_value)? * grade_break,
+        };
+        let k_value = (grade_break != 0.0).then(|| curve_length / grade_break);
         let a = (outgoing_grade - incoming_grade) / (2.0 * curve_length);
         let external = a * (curve_length / 2.0).powi(2);
         let design_speed = coerce_speed(&self.input_design_speed).unwrap_or_default();
@@ -77,36 +110,51 @@ impl VerticalData {
             incoming_grade,
             outgoing_grade,
             curve_length,
+            k_value,
             external,
             design_speed,
             sustained_downgrade,
         })
     }
 
-    fn to_stations(&self, dimensions: &VerticalDimensions) -> Result<VerticalStations> {
+    pub fn to_equations(&self) -> Result<StationEquations> {
+        Ok(coerce_station_equations(&self.input_station_equations)?)
+    }
+
+    fn to_stations(
+        &self,
+        dimensions: &VerticalDimensions,
+        equations: &StationEquations,
+    ) -> Result<VerticalStations> {
         let starting_station = Station {
-            value: coerce_station_value(&self.input_station)?,
+            value: equations.parse(&self.input_station)?,
             elevation: Some(coerce_elevation(&self.input_elevation)?),
             ..Default::default()
         };
 
-        match self.input_method {
-            VerticalDefinition::PVC => Ok(VerticalStations {
+        let stations = match self.input_method {
+            VerticalDefinition::PVC => VerticalStations {
                 pvc: starting_station,
                 pvi: self.pvc_to_pvi(starting_station, dimensions),
                 pvt: self.pvc_to_pvt(starting_station, dimensions),
-            }),
-            VerticalDefinition::PVI => Ok(VerticalStations {
+            },
+            VerticalDefinition::PVI => VerticalStations {
                 pvc: self.pvi_to_pvc(starting_station, dimensions),
                 pvi: starting_station,
                 pvt: self.pvi_to_pvt(starting_station, dimensions),
-            }),
-            VerticalDefinition::PVT => Ok(VerticalStations {
+            },
+            VerticalDefinition::PVT => VerticalStations {
                 pvc: self.pvt_to_pvc(starting_station, dimensions),
                 pvi: self.pvt_to_pvi(starting_station, dimensions),
                 pvt: starting_station,
-            }),
-        }
+            },
+        };
+
+        Ok(VerticalStations {
+            pvc: equations.tag(stations.pvc),
+            pvi: equations.tag(stations.pvi),
+            pvt: equations.tag(stations.pvt),
+        })
     }
 
     fn pvc_to_pvi(&self, sts: Station, dim: &VerticalDimensions) -> Station {
@@ -167,11 +215,13 @@ impl VerticalData {
 
     pub fn to_vertical_curve(&self) -> Result<VerticalCurve> {
         let dimensions = self.to_dimensions()?;
-        let stations = self.to_stations(&dimensions)?;
+        let equations = self.to_equations()?;
+        let stations = self.to_stations(&dimensions, &equations)?;
 
         Ok(VerticalCurve {
             dimensions,
             stations,
+            equations,
         })
     }
 }
@@ -179,9 +229,9 @@ impl VerticalData {
 #[cfg(test)]
 mod vertical_tests {
 
-    use crate::datatypes::Station;
+    use crate::datatypes::{ObstacleDetail, Station};
 
-    use super::{VerticalData, VerticalDefinition};
+    use super::{ObstacleType, VerticalData, VerticalDefinition, VerticalLengthDefinition};
 
     #[test]
     fn v1() {
@@ -332,4 +382,89 @@ mod vertical_tests {
             }
         );
     }
+
+    #[test]
+    fn v6() {
+        let data = VerticalData {
+            input_method: VerticalDefinition::PVI,
+            input_station: "0+00".to_string(),
+            input_elevation: "1001.38".to_string(),
+            input_incoming_grade: "0.44%".to_string(),
+            input_outgoing_grade: "-0.56%".to_string(),
+            input_length_method: VerticalLengthDefinition::K,
+            input_k_value: "500".to_string(),
+            ..Default::default()
+        };
+        let curve = data.to_vertical_curve().unwrap();
+        assert!((curve.dimensions.curve_length - 500.).abs() < 1e-9);
+        assert!((curve.dimensions.k_value.unwrap() - 500.).abs() < 1e-9);
+        assert!((curve.stations.pvc.value + 250.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn equal_grades() {
+        let mut data = VerticalData {
+            input_method: VerticalDefinition::PVI,
+            input_station: "0+00".to_string(),
+            input_elevation: "1000".to_string(),
+            input_incoming_grade: "1%".to_string(),
+            input_outgoing_grade: "1%".to_string(),
+            input_length: "400".to_string(),
+            input_length_method: VerticalLengthDefinition::K,
+            input_k_value: "100".to_string(),
+            ..Default::default()
+        };
+        assert!(data.to_vertical_curve().is_err());
+
+        data.input_length_method = VerticalLengthDefinition::Length;
+        let curve = data.to_vertical_curve().unwrap();
+        assert!(curve.dimensions.k_value.is_none());
+        assert!(curve.dimensions.external.abs() < 1e-9);
+        assert!((curve.stations.pvt.elevation.unwrap() - 1002.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn v7() {
+        // PVC 124+00, PVT 128+00 with a 40.22 overlap at 125+00 to 125+40.22
+        let data = VerticalData {
+            input_method: VerticalDefinition::PVC,
+            input_station: "124+00".to_string(),
+            input_elevation: "1000".to_string(),
+            input_incoming_grade: "2%".to_string(),
+            input_outgoing_grade: "-2%".to_string(),
+            input_length: "400".to_string(),
+            input_station_equations: "125+40.22 = 125+00".to_string(),
+            ..Default::default()
+        };
+        let curve = data.to_vertical_curve().unwrap();
+        assert_eq!(
+            format!("{}", curve.stations.pvt),
+            "STA: 127+59.78 ELEV: 1000.00"
+        );
+
+        let interval = curve.interval_stations(25.);
+        let labels = interval
+            .interval
+            .iter()
+            .map(|x| format!("{}", x).split(" ELEV").next().unwrap().to_string())
+            .collect::<Vec<String>>();
+        assert!(labels.contains(&"STA: 125+25.00 BK".to_string()));
+        assert!(labels.contains(&"STA: 125+25.00 AH".to_string()));
+        assert_eq!(labels.len(), 17);
+
+        // an obstacle at the repeated station resolves to the ahead side
+        let equations = data.to_equations().unwrap();
+        assert!(equations.parse("125+25").is_err());
+        let obstacle = Station {
+            value: equations.parse("125+25 AH").unwrap(),
+            elevation: Some(1010.),
+            ..Default::default()
+        };
+        let obstacles = ObstacleDetail {
+            interval: vec![(obstacle, ObstacleType::Above)],
+        };
+        let spot = curve.obstacle_compliant(&obstacles)[0].as_ref().unwrap().2;
+        let ahead = interval.interval.iter().find(|x| x.value == obstacle.value);
+        assert_eq!(spot.elevation, ahead.unwrap().elevation);
+    }
 }
@@ -61,4 +61,36 @@ mod hori_tests {
             Err(e) => println!("{}", e),
         }
     }
+
+    #[test]
+    fn major_stations() {
+        // the same curve given by its PC, PI and PT
+        for (method, station) in [
+            (HorizontalStationDefinition::PC, "100+10"),
+            (HorizontalStationDefinition::PI, "103+73.97"),
+            (HorizontalStationDefinition::PT, "107+08.13"),
+        ] {
+            let horizontal_data = HorizontalData {
+                input_station_method: method,
+                input_build_method: HorizontalBuildDefinition::RadiusCurveAngle,
+                input_station: station.to_string(),
+                input_radius: "1000".to_string(),
+                input_curve_angle: "40".to_string(),
+                ..Default::default()
+            };
+            let curve = horizontal_data.to_horizontal_curve().unwrap();
+            let (stations, dimensions) = (curve.stations, curve.dimensions);
+
+            assert!((stations.pi.value - stations.pc.value - dimensions.tangent).abs() < 1e-6);
+            assert!((stations.pt.value - stations.pc.value - dimensions.curve_length).abs() < 1e-6);
+
+            let detail = curve.interval_stations(50.0);
+            let pt = detail.interval.last().unwrap();
+            assert!((pt.value - stations.pt.value).abs() < 1e-9);
+            assert!(
+                (pt.deflection.unwrap().radians - dimensions.curve_angle.radians / 2.0).abs()
+                    < 1e-9
+            );
+        }
+    }
 }
@@ -1,2 +1,3 @@
 pub mod datatypes;
 pub mod horizontal;
+pub mod vertical;
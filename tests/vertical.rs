@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod vert_tests {
+    use floracad::vertical::{VerticalData, VerticalDefinition};
+
+    #[test]
+    fn interval_elevations() {
+        // the PVC is off the interval so the first interval station isn't the PVC
+        let vertical_data = VerticalData {
+            input_method: VerticalDefinition::PVC,
+            input_station: "100+10".to_string(),
+            input_elevation: "500".to_string(),
+            input_incoming_grade: "2%".to_string(),
+            input_outgoing_grade: "-1%".to_string(),
+            input_length: "400".to_string(),
+            ..Default::default()
+        };
+        let curve = vertical_data.to_vertical_curve().unwrap();
+        let (pvc, dimensions) = (curve.stations.pvc, curve.dimensions);
+        let a = (dimensions.outgoing_grade - dimensions.incoming_grade)
+            / (2.0 * dimensions.curve_length);
+
+        let detail = curve.interval_stations(50.0);
+        assert!(detail.interval.len() > 2);
+        for station in detail.interval {
+            let x = station.value - pvc.value;
+            let elevation = pvc.elevation.unwrap() + dimensions.incoming_grade * x + a * x.powi(2);
+            assert!((station.elevation.unwrap() - elevation).abs() < 1e-9);
+        }
+    }
+}